@@ -0,0 +1,14 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    #[cfg(feature = "capi-header")]
+    {
+        println!("cargo:rerun-if-changed=src/lib.rs");
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+
+        let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+        cbindgen::generate(&crate_dir)
+            .expect("unable to generate C bindings")
+            .write_to_file(format!("{}/include/dhcpv6_parser.h", crate_dir));
+    }
+}
@@ -0,0 +1,212 @@
+#ifndef DHCPV6_PARSER_H
+#define DHCPV6_PARSER_H
+
+/* Generated by cbindgen from src/lib.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Status codes returned by every fallible function of the C API.
+ */
+typedef enum Dhcpv6Status {
+  DHCPV6_STATUS_OK = 0,
+  DHCPV6_STATUS_NULL_POINTER,
+  DHCPV6_STATUS_OUT_OF_RANGE,
+  DHCPV6_STATUS_WRONG_OPTION_TYPE,
+  DHCPV6_STATUS_TRUNCATED_HEADER,
+  DHCPV6_STATUS_TRUNCATED_OPTION,
+  DHCPV6_STATUS_BAD_OPTION_LENGTH,
+  DHCPV6_STATUS_INVALID_UTF8,
+  DHCPV6_STATUS_INVALID_DOMAIN_NAME,
+  DHCPV6_STATUS_INVALID_PREFIX,
+  DHCPV6_STATUS_UNKNOWN_MESSAGE_TYPE,
+  DHCPV6_STATUS_UNKNOWN_OPTION,
+  DHCPV6_STATUS_TRAILING_BYTES,
+  DHCPV6_STATUS_NESTING_TOO_DEEP,
+  DHCPV6_STATUS_MISSING_RELAY_MESSAGE,
+  DHCPV6_STATUS_TOO_MANY_RELAY_HOPS,
+  DHCPV6_STATUS_MALFORMED,
+  DHCPV6_STATUS_TOO_MANY_OPTIONS,
+  DHCPV6_STATUS_BAD_SUBOPTION_LENGTH,
+} Dhcpv6Status;
+
+/**
+ * Opaque handle on a parsed message, owning a copy of the packet.
+ */
+typedef struct Dhcpv6Message Dhcpv6Message;
+
+/**
+ * Fixed header fields. Fields that do not apply to the message layout are
+ * zeroed.
+ */
+typedef struct Dhcpv6HeaderInfo {
+  uint8_t message_type;
+  bool is_relay;
+  uint32_t transaction_id;
+  uint32_t flags;
+  uint8_t hop_count;
+  uint8_t link_address[16];
+  uint8_t peer_address[16];
+} Dhcpv6HeaderInfo;
+
+/**
+ * Raw view of an option. `data` points inside the message and stays valid
+ * until the message is freed.
+ */
+typedef struct Dhcpv6OptionInfo {
+  uint16_t code;
+  const uint8_t *data;
+  size_t len;
+} Dhcpv6OptionInfo;
+
+/**
+ * Fields of an IA_NA, IA_TA or IA_PD option. `t1` and `t2` are zero for
+ * IA_TA.
+ */
+typedef struct Dhcpv6IaInfo {
+  uint32_t iaid;
+  uint32_t t1;
+  uint32_t t2;
+} Dhcpv6IaInfo;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Parses `len` bytes at `data` into a newly allocated message stored in
+ * `*message`. On failure, `*error_offset` receives the offset of the
+ * faulty byte if `error_offset` is not NULL.
+ *
+ * # Safety
+ *
+ * `data` must point to `len` readable bytes and `message` to writable
+ * storage for a pointer.
+ */
+enum Dhcpv6Status dhcpv6_message_parse(const uint8_t *data,
+                                       size_t len,
+                                       struct Dhcpv6Message **message,
+                                       size_t *error_offset);
+
+/**
+ * Releases a message returned by `dhcpv6_message_parse`. Passing NULL is a
+ * no-op.
+ *
+ * # Safety
+ *
+ * `message` must be NULL or a pointer obtained from `dhcpv6_message_parse`
+ * that has not been freed yet.
+ */
+void dhcpv6_message_free(struct Dhcpv6Message *message);
+
+/**
+ * Copies the fixed header fields of `message` into `*header`.
+ *
+ * # Safety
+ *
+ * `message` must be a live message and `header` writable.
+ */
+enum Dhcpv6Status dhcpv6_message_header(const struct Dhcpv6Message *message,
+                                        struct Dhcpv6HeaderInfo *header);
+
+/**
+ * Returns the number of top-level options of `message`, or 0 if `message`
+ * is NULL.
+ *
+ * # Safety
+ *
+ * `message` must be NULL or a live message.
+ */
+size_t dhcpv6_message_option_count(const struct Dhcpv6Message *message);
+
+/**
+ * Fetches the code and raw value of the option at `index`.
+ *
+ * # Safety
+ *
+ * `message` must be a live message and `option` writable.
+ */
+enum Dhcpv6Status dhcpv6_message_option(const struct Dhcpv6Message *message,
+                                        size_t index,
+                                        struct Dhcpv6OptionInfo *option);
+
+/**
+ * Finds the first top-level option with the given `code` and stores its
+ * position in `*index`.
+ *
+ * # Safety
+ *
+ * `message` must be a live message and `index` writable.
+ */
+enum Dhcpv6Status dhcpv6_message_find_option(const struct Dhcpv6Message *message,
+                                             uint16_t code,
+                                             size_t *index);
+
+/**
+ * Reads the value of an Elapsed Time option.
+ *
+ * # Safety
+ *
+ * `message` must be a live message and `elapsed_time` writable.
+ */
+enum Dhcpv6Status dhcpv6_option_elapsed_time(const struct Dhcpv6Message *message,
+                                             size_t index,
+                                             uint16_t *elapsed_time);
+
+/**
+ * Reads the value of a Preference option.
+ *
+ * # Safety
+ *
+ * `message` must be a live message and `preference` writable.
+ */
+enum Dhcpv6Status dhcpv6_option_preference(const struct Dhcpv6Message *message,
+                                           size_t index,
+                                           uint8_t *preference);
+
+/**
+ * Reads a Status Code option. `*text` points inside the message and is not
+ * NUL-terminated; its length is stored in `*text_len`.
+ *
+ * # Safety
+ *
+ * `message` must be a live message and the other pointers writable.
+ */
+enum Dhcpv6Status dhcpv6_option_status_code(const struct Dhcpv6Message *message,
+                                            size_t index,
+                                            uint16_t *status,
+                                            const uint8_t **text,
+                                            size_t *text_len);
+
+/**
+ * Reads the fixed fields of an IA_NA, IA_TA or IA_PD option.
+ *
+ * # Safety
+ *
+ * `message` must be a live message and `ia` writable.
+ */
+enum Dhcpv6Status dhcpv6_option_ia(const struct Dhcpv6Message *message,
+                                   size_t index,
+                                   struct Dhcpv6IaInfo *ia);
+
+/**
+ * Reads the DUID type of a Client Identifier or Server Identifier option.
+ * The raw DUID is available through `dhcpv6_message_option`.
+ *
+ * # Safety
+ *
+ * `message` must be a live message and `duid_type` writable.
+ */
+enum Dhcpv6Status dhcpv6_option_duid_type(const struct Dhcpv6Message *message,
+                                          size_t index,
+                                          uint16_t *duid_type);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* DHCPV6_PARSER_H */
@@ -0,0 +1,546 @@
+use std::ptr;
+use std::slice;
+
+use dhcpv6_parser::error::ErrorKind;
+use dhcpv6_parser::structs::header::{parse_message, DHCPv6Header};
+use dhcpv6_parser::structs::options::{parse_dhcpv6_option, DHCPv6Option};
+
+/// Status codes returned by every fallible function of the C API.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Dhcpv6Status {
+    Ok = 0,
+    NullPointer,
+    OutOfRange,
+    WrongOptionType,
+    TruncatedHeader,
+    TruncatedOption,
+    BadOptionLength,
+    InvalidUtf8,
+    InvalidDomainName,
+    InvalidPrefix,
+    UnknownMessageType,
+    UnknownOption,
+    TrailingBytes,
+    NestingTooDeep,
+    MissingRelayMessage,
+    TooManyRelayHops,
+    Malformed,
+    TooManyOptions,
+    BadSuboptionLength,
+}
+
+impl From<&ErrorKind> for Dhcpv6Status {
+    fn from(kind: &ErrorKind) -> Self {
+        match kind {
+            ErrorKind::TruncatedHeader => Self::TruncatedHeader,
+            ErrorKind::TruncatedOption => Self::TruncatedOption,
+            ErrorKind::BadOptionLength { .. } => Self::BadOptionLength,
+            ErrorKind::BadSuboptionLength { .. } => Self::BadSuboptionLength,
+            ErrorKind::InvalidUtf8 => Self::InvalidUtf8,
+            ErrorKind::InvalidDomainName => Self::InvalidDomainName,
+            ErrorKind::InvalidPrefix { .. } => Self::InvalidPrefix,
+            ErrorKind::UnknownMessageType(_) => Self::UnknownMessageType,
+            ErrorKind::UnknownOption(_) => Self::UnknownOption,
+            ErrorKind::TrailingBytes => Self::TrailingBytes,
+            ErrorKind::NestingTooDeep => Self::NestingTooDeep,
+            ErrorKind::MissingRelayMessage => Self::MissingRelayMessage,
+            ErrorKind::TooManyRelayHops => Self::TooManyRelayHops,
+            ErrorKind::TooManyOptions => Self::TooManyOptions,
+            ErrorKind::Nom(_) => Self::Malformed,
+        }
+    }
+}
+
+/// Opaque handle on a parsed message, owning a copy of the packet.
+pub struct Dhcpv6Message {
+    data: Vec<u8>,
+    header: Dhcpv6HeaderInfo,
+    options: Vec<usize>,
+}
+
+/// Fixed header fields. Fields that do not apply to the message layout are
+/// zeroed.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Dhcpv6HeaderInfo {
+    pub message_type: u8,
+    pub is_relay: bool,
+    pub transaction_id: u32,
+    pub flags: u32,
+    pub hop_count: u8,
+    pub link_address: [u8; 16],
+    pub peer_address: [u8; 16],
+}
+
+/// Raw view of an option. `data` points inside the message and stays valid
+/// until the message is freed.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct Dhcpv6OptionInfo {
+    pub code: u16,
+    pub data: *const u8,
+    pub len: usize,
+}
+
+/// Fields of an IA_NA, IA_TA or IA_PD option. `t1` and `t2` are zero for
+/// IA_TA.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Dhcpv6IaInfo {
+    pub iaid: u32,
+    pub t1: u32,
+    pub t2: u32,
+}
+
+impl Dhcpv6Message {
+    fn from_bytes(data: Vec<u8>) -> Result<Self, dhcpv6_parser::error::Error> {
+        let (header, start) = match parse_message(&data[..])? {
+            DHCPv6Header::ClientServer {
+                message_type,
+                transaction_id,
+                ..
+            } => (
+                Dhcpv6HeaderInfo {
+                    message_type: message_type.to_u8(),
+                    transaction_id,
+                    ..Dhcpv6HeaderInfo::default()
+                },
+                4,
+            ),
+            DHCPv6Header::DHCPv4Over6 {
+                message_type,
+                flags,
+                ..
+            } => (
+                Dhcpv6HeaderInfo {
+                    message_type: message_type.to_u8(),
+                    flags,
+                    ..Dhcpv6HeaderInfo::default()
+                },
+                4,
+            ),
+            DHCPv6Header::RelayAgentServer {
+                message_type,
+                hop_count,
+                link_address,
+                peer_address,
+                ..
+            } => (
+                Dhcpv6HeaderInfo {
+                    message_type: message_type.to_u8(),
+                    is_relay: true,
+                    hop_count,
+                    link_address: link_address.octets(),
+                    peer_address: peer_address.octets(),
+                    ..Dhcpv6HeaderInfo::default()
+                },
+                34,
+            ),
+        };
+
+        let mut options = Vec::new();
+        let mut offset = start;
+        while offset + 4 <= data.len() {
+            options.push(offset);
+            offset += 4 + u16::from_be_bytes([data[offset + 2], data[offset + 3]]) as usize;
+        }
+
+        Ok(Self {
+            data,
+            header,
+            options,
+        })
+    }
+
+    fn option(&self, index: usize) -> Result<DHCPv6Option<'_>, Dhcpv6Status> {
+        let offset = *self.options.get(index).ok_or(Dhcpv6Status::OutOfRange)?;
+        parse_dhcpv6_option(&self.data[offset..])
+            .map(|(_, option)| option)
+            .map_err(|_| Dhcpv6Status::Malformed)
+    }
+}
+
+macro_rules! try_status {
+    ($e:expr) => {
+        match $e {
+            Ok(value) => value,
+            Err(status) => return status,
+        }
+    };
+}
+
+unsafe fn as_message<'a>(message: *const Dhcpv6Message) -> Result<&'a Dhcpv6Message, Dhcpv6Status> {
+    message.as_ref().ok_or(Dhcpv6Status::NullPointer)
+}
+
+/// Parses `len` bytes at `data` into a newly allocated message stored in
+/// `*message`. On failure, `*error_offset` receives the offset of the
+/// faulty byte if `error_offset` is not NULL.
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes and `message` to writable
+/// storage for a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn dhcpv6_message_parse(
+    data: *const u8,
+    len: usize,
+    message: *mut *mut Dhcpv6Message,
+    error_offset: *mut usize,
+) -> Dhcpv6Status {
+    if data.is_null() || message.is_null() {
+        return Dhcpv6Status::NullPointer;
+    }
+
+    let bytes = slice::from_raw_parts(data, len).to_vec();
+    match Dhcpv6Message::from_bytes(bytes) {
+        Ok(parsed) => {
+            *message = Box::into_raw(Box::new(parsed));
+            Dhcpv6Status::Ok
+        }
+        Err(e) => {
+            *message = ptr::null_mut();
+            if !error_offset.is_null() {
+                *error_offset = e.offset;
+            }
+            Dhcpv6Status::from(&e.kind)
+        }
+    }
+}
+
+/// Releases a message returned by `dhcpv6_message_parse`. Passing NULL is a
+/// no-op.
+///
+/// # Safety
+///
+/// `message` must be NULL or a pointer obtained from `dhcpv6_message_parse`
+/// that has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn dhcpv6_message_free(message: *mut Dhcpv6Message) {
+    if !message.is_null() {
+        drop(Box::from_raw(message));
+    }
+}
+
+/// Copies the fixed header fields of `message` into `*header`.
+///
+/// # Safety
+///
+/// `message` must be a live message and `header` writable.
+#[no_mangle]
+pub unsafe extern "C" fn dhcpv6_message_header(
+    message: *const Dhcpv6Message,
+    header: *mut Dhcpv6HeaderInfo,
+) -> Dhcpv6Status {
+    let message = try_status!(as_message(message));
+    if header.is_null() {
+        return Dhcpv6Status::NullPointer;
+    }
+
+    *header = message.header;
+    Dhcpv6Status::Ok
+}
+
+/// Returns the number of top-level options of `message`, or 0 if `message`
+/// is NULL.
+///
+/// # Safety
+///
+/// `message` must be NULL or a live message.
+#[no_mangle]
+pub unsafe extern "C" fn dhcpv6_message_option_count(message: *const Dhcpv6Message) -> usize {
+    as_message(message).map_or(0, |message| message.options.len())
+}
+
+/// Fetches the code and raw value of the option at `index`.
+///
+/// # Safety
+///
+/// `message` must be a live message and `option` writable.
+#[no_mangle]
+pub unsafe extern "C" fn dhcpv6_message_option(
+    message: *const Dhcpv6Message,
+    index: usize,
+    option: *mut Dhcpv6OptionInfo,
+) -> Dhcpv6Status {
+    let message = try_status!(as_message(message));
+    if option.is_null() {
+        return Dhcpv6Status::NullPointer;
+    }
+    let offset = try_status!(message
+        .options
+        .get(index)
+        .copied()
+        .ok_or(Dhcpv6Status::OutOfRange));
+
+    let data = &message.data[offset..];
+    let len = u16::from_be_bytes([data[2], data[3]]) as usize;
+    *option = Dhcpv6OptionInfo {
+        code: u16::from_be_bytes([data[0], data[1]]),
+        data: data[4..].as_ptr(),
+        len,
+    };
+    Dhcpv6Status::Ok
+}
+
+/// Finds the first top-level option with the given `code` and stores its
+/// position in `*index`.
+///
+/// # Safety
+///
+/// `message` must be a live message and `index` writable.
+#[no_mangle]
+pub unsafe extern "C" fn dhcpv6_message_find_option(
+    message: *const Dhcpv6Message,
+    code: u16,
+    index: *mut usize,
+) -> Dhcpv6Status {
+    let message = try_status!(as_message(message));
+    if index.is_null() {
+        return Dhcpv6Status::NullPointer;
+    }
+
+    match message
+        .options
+        .iter()
+        .position(|offset| message.data[*offset..*offset + 2] == code.to_be_bytes())
+    {
+        Some(position) => {
+            *index = position;
+            Dhcpv6Status::Ok
+        }
+        None => Dhcpv6Status::OutOfRange,
+    }
+}
+
+/// Reads the value of an Elapsed Time option.
+///
+/// # Safety
+///
+/// `message` must be a live message and `elapsed_time` writable.
+#[no_mangle]
+pub unsafe extern "C" fn dhcpv6_option_elapsed_time(
+    message: *const Dhcpv6Message,
+    index: usize,
+    elapsed_time: *mut u16,
+) -> Dhcpv6Status {
+    let message = try_status!(as_message(message));
+    if elapsed_time.is_null() {
+        return Dhcpv6Status::NullPointer;
+    }
+
+    match try_status!(message.option(index)) {
+        DHCPv6Option::ElapstedTime {
+            elapsed_time: value,
+        } => {
+            *elapsed_time = value;
+            Dhcpv6Status::Ok
+        }
+        _ => Dhcpv6Status::WrongOptionType,
+    }
+}
+
+/// Reads the value of a Preference option.
+///
+/// # Safety
+///
+/// `message` must be a live message and `preference` writable.
+#[no_mangle]
+pub unsafe extern "C" fn dhcpv6_option_preference(
+    message: *const Dhcpv6Message,
+    index: usize,
+    preference: *mut u8,
+) -> Dhcpv6Status {
+    let message = try_status!(as_message(message));
+    if preference.is_null() {
+        return Dhcpv6Status::NullPointer;
+    }
+
+    match try_status!(message.option(index)) {
+        DHCPv6Option::Preference { pref_value } => {
+            *preference = pref_value;
+            Dhcpv6Status::Ok
+        }
+        _ => Dhcpv6Status::WrongOptionType,
+    }
+}
+
+/// Reads a Status Code option. `*text` points inside the message and is not
+/// NUL-terminated; its length is stored in `*text_len`.
+///
+/// # Safety
+///
+/// `message` must be a live message and the other pointers writable.
+#[no_mangle]
+pub unsafe extern "C" fn dhcpv6_option_status_code(
+    message: *const Dhcpv6Message,
+    index: usize,
+    status: *mut u16,
+    text: *mut *const u8,
+    text_len: *mut usize,
+) -> Dhcpv6Status {
+    let message = try_status!(as_message(message));
+    if status.is_null() || text.is_null() || text_len.is_null() {
+        return Dhcpv6Status::NullPointer;
+    }
+
+    match try_status!(message.option(index)) {
+        DHCPv6Option::StatusCode {
+            code,
+            message: value,
+        } => {
+            *status = code.to_u16();
+            *text = value.as_ptr();
+            *text_len = value.len();
+            Dhcpv6Status::Ok
+        }
+        _ => Dhcpv6Status::WrongOptionType,
+    }
+}
+
+/// Reads the fixed fields of an IA_NA, IA_TA or IA_PD option.
+///
+/// # Safety
+///
+/// `message` must be a live message and `ia` writable.
+#[no_mangle]
+pub unsafe extern "C" fn dhcpv6_option_ia(
+    message: *const Dhcpv6Message,
+    index: usize,
+    ia: *mut Dhcpv6IaInfo,
+) -> Dhcpv6Status {
+    let message = try_status!(as_message(message));
+    if ia.is_null() {
+        return Dhcpv6Status::NullPointer;
+    }
+
+    *ia = match try_status!(message.option(index)) {
+        DHCPv6Option::IdentityAssociationForNonTemporaryAddresses {
+            id, time_1, time_2, ..
+        }
+        | DHCPv6Option::IdentityAssociationForPrefixDelegation {
+            id, time_1, time_2, ..
+        } => Dhcpv6IaInfo {
+            iaid: id,
+            t1: time_1,
+            t2: time_2,
+        },
+        DHCPv6Option::IdentityAssociationForTemporaryAddresses { id, .. } => Dhcpv6IaInfo {
+            iaid: id,
+            ..Dhcpv6IaInfo::default()
+        },
+        _ => return Dhcpv6Status::WrongOptionType,
+    };
+    Dhcpv6Status::Ok
+}
+
+/// Reads the DUID type of a Client Identifier or Server Identifier option.
+/// The raw DUID is available through `dhcpv6_message_option`.
+///
+/// # Safety
+///
+/// `message` must be a live message and `duid_type` writable.
+#[no_mangle]
+pub unsafe extern "C" fn dhcpv6_option_duid_type(
+    message: *const Dhcpv6Message,
+    index: usize,
+    duid_type: *mut u16,
+) -> Dhcpv6Status {
+    let message = try_status!(as_message(message));
+    if duid_type.is_null() {
+        return Dhcpv6Status::NullPointer;
+    }
+
+    match try_status!(message.option(index)) {
+        DHCPv6Option::CliendID { duid } | DHCPv6Option::ServerID { duid } => {
+            *duid_type = duid.duid_type();
+            Dhcpv6Status::Ok
+        }
+        _ => Dhcpv6Status::WrongOptionType,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ffi_parse_and_options() {
+        let input = b"\x01\x12\x34\x56\
+                      \x00\x01\x00\x0a\x00\x03\x00\x01\x00\x11\x22\xaa\xbb\xcc\
+                      \x00\x08\x00\x02\x00\x2a\
+                      \x00\x03\x00\x0c\x00\x00\x00\x07\x00\x00\x00\x00\x00\x00\x00\x00";
+        let mut message = ptr::null_mut();
+
+        unsafe {
+            assert_eq!(
+                dhcpv6_message_parse(input.as_ptr(), input.len(), &mut message, ptr::null_mut()),
+                Dhcpv6Status::Ok
+            );
+
+            let mut header = Dhcpv6HeaderInfo::default();
+            assert_eq!(
+                dhcpv6_message_header(message, &mut header),
+                Dhcpv6Status::Ok
+            );
+            assert_eq!(header.message_type, 1);
+            assert_eq!(header.transaction_id, 0x123456);
+            assert_eq!(dhcpv6_message_option_count(message), 3);
+
+            let mut index = 0;
+            assert_eq!(
+                dhcpv6_message_find_option(message, 8, &mut index),
+                Dhcpv6Status::Ok
+            );
+            assert_eq!(index, 1);
+            let mut elapsed_time = 0;
+            assert_eq!(
+                dhcpv6_option_elapsed_time(message, index, &mut elapsed_time),
+                Dhcpv6Status::Ok
+            );
+            assert_eq!(elapsed_time, 42);
+
+            let mut duid_type = 0;
+            assert_eq!(
+                dhcpv6_option_duid_type(message, 0, &mut duid_type),
+                Dhcpv6Status::Ok
+            );
+            assert_eq!(duid_type, 3);
+
+            let mut ia = Dhcpv6IaInfo::default();
+            assert_eq!(dhcpv6_option_ia(message, 2, &mut ia), Dhcpv6Status::Ok);
+            assert_eq!(ia.iaid, 7);
+            assert_eq!(
+                dhcpv6_option_ia(message, 1, &mut ia),
+                Dhcpv6Status::WrongOptionType
+            );
+            assert_eq!(
+                dhcpv6_option_ia(message, 3, &mut ia),
+                Dhcpv6Status::OutOfRange
+            );
+
+            dhcpv6_message_free(message);
+        }
+    }
+
+    #[test]
+    fn test_ffi_parse_error() {
+        let input = b"\x01\x00\x00\x01\x00\x08\x00\x03\x00\x00\x00";
+        let mut message = ptr::null_mut();
+        let mut offset = 0;
+
+        unsafe {
+            assert_eq!(
+                dhcpv6_message_parse(input.as_ptr(), input.len(), &mut message, &mut offset),
+                Dhcpv6Status::BadOptionLength
+            );
+            assert!(message.is_null());
+            assert_eq!(offset, 4);
+            assert_eq!(
+                dhcpv6_message_parse(ptr::null(), 0, &mut message, &mut offset),
+                Dhcpv6Status::NullPointer
+            );
+        }
+    }
+}
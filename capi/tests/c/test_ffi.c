@@ -0,0 +1,89 @@
+#include <stdio.h>
+#include <string.h>
+
+#include "dhcpv6_parser.h"
+
+#define CHECK(cond)                                                  \
+    do {                                                             \
+        if (!(cond)) {                                               \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,   \
+                    __LINE__, #cond);                                \
+            return 1;                                                \
+        }                                                            \
+    } while (0)
+
+static const uint8_t reply[] = {
+    0x07, 0x12, 0x34, 0x56,
+    /* Server Identifier, DUID-LL */
+    0x00, 0x02, 0x00, 0x0a, 0x00, 0x03, 0x00, 0x01,
+    0x00, 0x11, 0x22, 0xaa, 0xbb, 0xcc,
+    /* Preference */
+    0x00, 0x07, 0x00, 0x01, 0xff,
+    /* IA_NA */
+    0x00, 0x03, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x01,
+    0x00, 0x00, 0x0e, 0x10, 0x00, 0x00, 0x15, 0x18,
+    /* Status Code */
+    0x00, 0x0d, 0x00, 0x04, 0x00, 0x00, 'o', 'k',
+};
+
+static const uint8_t truncated[] = {
+    0x01, 0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x0a, 0x00, 0x03,
+};
+
+int main(void)
+{
+    Dhcpv6Message *message = NULL;
+    Dhcpv6HeaderInfo header;
+    Dhcpv6OptionInfo option;
+    Dhcpv6IaInfo ia;
+    size_t index, offset = 0, text_len;
+    uint16_t status, duid_type;
+    const uint8_t *text;
+    uint8_t preference;
+
+    CHECK(dhcpv6_message_parse(reply, sizeof(reply), &message, NULL) ==
+          DHCPV6_STATUS_OK);
+    CHECK(message != NULL);
+
+    CHECK(dhcpv6_message_header(message, &header) == DHCPV6_STATUS_OK);
+    CHECK(header.message_type == 7);
+    CHECK(!header.is_relay);
+    CHECK(header.transaction_id == 0x123456);
+
+    CHECK(dhcpv6_message_option_count(message) == 4);
+    for (index = 0; index < dhcpv6_message_option_count(message); index++) {
+        CHECK(dhcpv6_message_option(message, index, &option) ==
+              DHCPV6_STATUS_OK);
+    }
+    CHECK(option.code == 13);
+    CHECK(option.len == 4);
+    CHECK(dhcpv6_message_option(message, index, &option) ==
+          DHCPV6_STATUS_OUT_OF_RANGE);
+
+    CHECK(dhcpv6_option_duid_type(message, 0, &duid_type) == DHCPV6_STATUS_OK);
+    CHECK(duid_type == 3);
+
+    CHECK(dhcpv6_message_find_option(message, 7, &index) == DHCPV6_STATUS_OK);
+    CHECK(dhcpv6_option_preference(message, index, &preference) ==
+          DHCPV6_STATUS_OK);
+    CHECK(preference == 255);
+
+    CHECK(dhcpv6_option_ia(message, 2, &ia) == DHCPV6_STATUS_OK);
+    CHECK(ia.iaid == 1 && ia.t1 == 3600 && ia.t2 == 5400);
+    CHECK(dhcpv6_option_ia(message, 1, &ia) ==
+          DHCPV6_STATUS_WRONG_OPTION_TYPE);
+
+    CHECK(dhcpv6_option_status_code(message, 3, &status, &text, &text_len) ==
+          DHCPV6_STATUS_OK);
+    CHECK(status == 0 && text_len == 2 && memcmp(text, "ok", 2) == 0);
+
+    dhcpv6_message_free(message);
+    dhcpv6_message_free(NULL);
+
+    CHECK(dhcpv6_message_parse(truncated, sizeof(truncated), &message,
+                               &offset) == DHCPV6_STATUS_TRUNCATED_OPTION);
+    CHECK(message == NULL);
+    CHECK(offset == 4);
+
+    return 0;
+}
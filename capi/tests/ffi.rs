@@ -0,0 +1,33 @@
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[test]
+fn test_c_program() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = env::current_exe()
+        .unwrap()
+        .parent()
+        .and_then(|deps| deps.parent())
+        .unwrap()
+        .to_path_buf();
+    let library = target_dir.join("libdhcpv6_parser_capi.a");
+    let program = target_dir.join("test_ffi");
+
+    let status = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg(manifest_dir.join("tests/c/test_ffi.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-o")
+        .arg(&program)
+        .arg(&library)
+        .args(["-lpthread", "-ldl", "-lm"])
+        .status()
+        .expect("failed to run the C compiler");
+    assert!(status.success());
+
+    let status = Command::new(&program).status().unwrap();
+    assert!(status.success());
+}
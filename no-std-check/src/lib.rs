@@ -0,0 +1,30 @@
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use dhcpv6_parser::header::{parse_message, DHCPv6Header};
+#[cfg(feature = "alloc")]
+use dhcpv6_parser::options::DHCPv6Option;
+
+use dhcpv6_parser::message_types::DHCPv6MessageType;
+use dhcpv6_parser::options_iter::parse_dhcpv6_message_options;
+
+pub fn client_id(packet: &[u8]) -> Option<(DHCPv6MessageType, &[u8])> {
+    let (_, (message_type, options)) = parse_dhcpv6_message_options(packet).ok()?;
+    let client_id = options.find(1).ok()??;
+
+    Some((message_type, client_id.value))
+}
+
+#[cfg(feature = "alloc")]
+pub fn options(packet: &[u8]) -> Option<Vec<DHCPv6Option<'_>>> {
+    match parse_message(packet).ok()? {
+        DHCPv6Header::ClientServer { options, .. }
+        | DHCPv6Header::RelayAgentServer { options, .. }
+        | DHCPv6Header::DHCPv4Over6 { options, .. } => Some(options),
+    }
+}
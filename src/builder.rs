@@ -0,0 +1,580 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::net::Ipv6Addr;
+
+use crate::encode::EncodeError;
+use crate::structs::duid::Duid;
+use crate::structs::header::{dhcpv6_header_len, encode_dhcpv6_header, DHCPv6Header};
+use crate::structs::link_layer::HardwareType;
+use crate::structs::message_types::DHCPv6MessageType;
+use crate::structs::options::DHCPv6Option;
+use crate::structs::status_code::StatusCode;
+
+#[derive(Debug, Clone)]
+pub struct MessageBuilder {
+    header: DHCPv6Header<'static>,
+}
+
+impl MessageBuilder {
+    pub fn new(message_type: DHCPv6MessageType, transaction_id: u32) -> Self {
+        Self {
+            header: DHCPv6Header::ClientServer {
+                message_type,
+                transaction_id,
+                options: Vec::new(),
+            },
+        }
+    }
+
+    pub fn solicit(transaction_id: u32) -> Self {
+        Self::new(DHCPv6MessageType::Solicit, transaction_id)
+    }
+
+    pub fn advertise(transaction_id: u32) -> Self {
+        Self::new(DHCPv6MessageType::Advertise, transaction_id)
+    }
+
+    pub fn request(transaction_id: u32) -> Self {
+        Self::new(DHCPv6MessageType::Request, transaction_id)
+    }
+
+    pub fn confirm(transaction_id: u32) -> Self {
+        Self::new(DHCPv6MessageType::Confirm, transaction_id)
+    }
+
+    pub fn renew(transaction_id: u32) -> Self {
+        Self::new(DHCPv6MessageType::Renew, transaction_id)
+    }
+
+    pub fn rebind(transaction_id: u32) -> Self {
+        Self::new(DHCPv6MessageType::Rebind, transaction_id)
+    }
+
+    pub fn reply(transaction_id: u32) -> Self {
+        Self::new(DHCPv6MessageType::Reply, transaction_id)
+    }
+
+    pub fn release(transaction_id: u32) -> Self {
+        Self::new(DHCPv6MessageType::Release, transaction_id)
+    }
+
+    pub fn decline(transaction_id: u32) -> Self {
+        Self::new(DHCPv6MessageType::Decline, transaction_id)
+    }
+
+    pub fn reconfigure(transaction_id: u32) -> Self {
+        Self::new(DHCPv6MessageType::Reconfigure, transaction_id)
+    }
+
+    pub fn information_request(transaction_id: u32) -> Self {
+        Self::new(DHCPv6MessageType::InformationRequest, transaction_id)
+    }
+
+    pub fn relay(
+        message_type: DHCPv6MessageType,
+        hop_count: u8,
+        link_address: Ipv6Addr,
+        peer_address: Ipv6Addr,
+    ) -> Self {
+        Self {
+            header: DHCPv6Header::RelayAgentServer {
+                message_type,
+                hop_count,
+                link_address,
+                peer_address,
+                options: Vec::new(),
+            },
+        }
+    }
+
+    pub fn relay_forward(hop_count: u8, link_address: Ipv6Addr, peer_address: Ipv6Addr) -> Self {
+        Self::relay(
+            DHCPv6MessageType::RelayForw,
+            hop_count,
+            link_address,
+            peer_address,
+        )
+    }
+
+    pub fn relay_reply(hop_count: u8, link_address: Ipv6Addr, peer_address: Ipv6Addr) -> Self {
+        Self::relay(
+            DHCPv6MessageType::RelayRepl,
+            hop_count,
+            link_address,
+            peer_address,
+        )
+    }
+
+    fn push(mut self, option: DHCPv6Option<'static>) -> Self {
+        match &mut self.header {
+            DHCPv6Header::ClientServer { options, .. }
+            | DHCPv6Header::RelayAgentServer { options, .. }
+            | DHCPv6Header::DHCPv4Over6 { options, .. } => options.push(option),
+        }
+        self
+    }
+
+    pub fn option(self, option: &DHCPv6Option) -> Self {
+        self.push(option.clone().into_owned())
+    }
+
+    pub fn client_id(self, duid: &Duid) -> Self {
+        self.option(&DHCPv6Option::CliendID { duid: duid.clone() })
+    }
+
+    pub fn server_id(self, duid: &Duid) -> Self {
+        self.option(&DHCPv6Option::ServerID { duid: duid.clone() })
+    }
+
+    pub fn ia_na<F>(self, id: u32, build: F) -> Self
+    where
+        F: FnOnce(IaNaBuilder) -> IaNaBuilder,
+    {
+        let ia = build(IaNaBuilder::default());
+        self.push(DHCPv6Option::IdentityAssociationForNonTemporaryAddresses {
+            id,
+            time_1: ia.time_1,
+            time_2: ia.time_2,
+            options: ia.options,
+        })
+    }
+
+    pub fn ia_ta<F>(self, id: u32, build: F) -> Self
+    where
+        F: FnOnce(IaTaBuilder) -> IaTaBuilder,
+    {
+        let ia = build(IaTaBuilder::default());
+        self.push(DHCPv6Option::IdentityAssociationForTemporaryAddresses {
+            id,
+            options: ia.options,
+        })
+    }
+
+    pub fn ia_pd<F>(self, id: u32, build: F) -> Self
+    where
+        F: FnOnce(IaPdBuilder) -> IaPdBuilder,
+    {
+        let ia = build(IaPdBuilder::default());
+        self.push(DHCPv6Option::IdentityAssociationForPrefixDelegation {
+            id,
+            time_1: ia.time_1,
+            time_2: ia.time_2,
+            options: ia.options,
+        })
+    }
+
+    pub fn oro(self, options: &[u16]) -> Self {
+        self.option(&DHCPv6Option::OptionRequest {
+            options: options.to_vec(),
+        })
+    }
+
+    pub fn preference(self, pref_value: u8) -> Self {
+        self.option(&DHCPv6Option::Preference { pref_value })
+    }
+
+    pub fn elapsed(self, elapsed_time: u16) -> Self {
+        self.option(&DHCPv6Option::ElapstedTime { elapsed_time })
+    }
+
+    pub fn server_unicast(self, address: Ipv6Addr) -> Self {
+        self.option(&DHCPv6Option::ServerUnicast { address })
+    }
+
+    pub fn status(self, code: StatusCode, message: &str) -> Self {
+        self.option(&DHCPv6Option::StatusCode {
+            code,
+            message: Cow::Borrowed(message.as_bytes()),
+        })
+    }
+
+    pub fn rapid_commit(self) -> Self {
+        self.option(&DHCPv6Option::RapidCommit {})
+    }
+
+    pub fn dns_servers(self, servers: &[Ipv6Addr]) -> Self {
+        self.option(&DHCPv6Option::DnsServers {
+            servers: servers.to_vec(),
+        })
+    }
+
+    pub fn interface_id(self, data: &[u8]) -> Self {
+        self.option(&DHCPv6Option::InterfaceID {
+            data: Cow::Borrowed(data),
+        })
+    }
+
+    pub fn remote_id(self, enterprise_number: u32, data: &[u8]) -> Self {
+        self.option(&DHCPv6Option::RemoteID {
+            enterprise_number,
+            data: Cow::Borrowed(data),
+        })
+    }
+
+    pub fn subscriber_id(self, data: &[u8]) -> Self {
+        self.option(&DHCPv6Option::SubscriberID {
+            data: Cow::Borrowed(data),
+        })
+    }
+
+    pub fn client_link_layer_address(self, hardware_type: HardwareType, address: &[u8]) -> Self {
+        self.option(&DHCPv6Option::ClientLinkLayerAddress {
+            hardware_type,
+            link_layer_address: Cow::Borrowed(address),
+        })
+    }
+
+    pub fn relay_message(self, data: &[u8]) -> Self {
+        self.option(&DHCPv6Option::RelayMessage {
+            data: Cow::Borrowed(data),
+        })
+    }
+
+    pub fn build(&self) -> Result<Vec<u8>, EncodeError> {
+        let mut output = Vec::with_capacity(dhcpv6_header_len(&self.header));
+        encode_dhcpv6_header(&self.header, &mut output)?;
+
+        Ok(output)
+    }
+}
+
+fn ia_address(
+    address: Ipv6Addr,
+    prefered_lifetime: u32,
+    valid_lifetime: u32,
+) -> DHCPv6Option<'static> {
+    DHCPv6Option::IdentityAssociationAddress {
+        address,
+        prefered_lifetime,
+        valid_lifetime,
+        options: Vec::new(),
+    }
+}
+
+fn ia_status(code: StatusCode, message: &str) -> DHCPv6Option<'static> {
+    DHCPv6Option::StatusCode {
+        code,
+        message: Cow::Owned(message.as_bytes().to_vec()),
+    }
+}
+
+// RFC 8415 section 21.4: IA_NA carries addresses and T1/T2.
+#[derive(Debug, Clone, Default)]
+pub struct IaNaBuilder {
+    time_1: u32,
+    time_2: u32,
+    options: Vec<DHCPv6Option<'static>>,
+}
+
+impl IaNaBuilder {
+    pub fn times(mut self, time_1: u32, time_2: u32) -> Self {
+        self.time_1 = time_1;
+        self.time_2 = time_2;
+        self
+    }
+
+    pub fn option(mut self, option: &DHCPv6Option) -> Self {
+        self.options.push(option.clone().into_owned());
+        self
+    }
+
+    pub fn address(
+        mut self,
+        address: Ipv6Addr,
+        prefered_lifetime: u32,
+        valid_lifetime: u32,
+    ) -> Self {
+        self.options
+            .push(ia_address(address, prefered_lifetime, valid_lifetime));
+        self
+    }
+
+    pub fn status(mut self, code: StatusCode, message: &str) -> Self {
+        self.options.push(ia_status(code, message));
+        self
+    }
+}
+
+// RFC 8415 section 21.5: IA_TA carries addresses but no T1/T2.
+#[derive(Debug, Clone, Default)]
+pub struct IaTaBuilder {
+    options: Vec<DHCPv6Option<'static>>,
+}
+
+impl IaTaBuilder {
+    pub fn option(mut self, option: &DHCPv6Option) -> Self {
+        self.options.push(option.clone().into_owned());
+        self
+    }
+
+    pub fn address(
+        mut self,
+        address: Ipv6Addr,
+        prefered_lifetime: u32,
+        valid_lifetime: u32,
+    ) -> Self {
+        self.options
+            .push(ia_address(address, prefered_lifetime, valid_lifetime));
+        self
+    }
+
+    pub fn status(mut self, code: StatusCode, message: &str) -> Self {
+        self.options.push(ia_status(code, message));
+        self
+    }
+}
+
+// RFC 8415 section 21.21: IA_PD carries prefixes and T1/T2.
+#[derive(Debug, Clone, Default)]
+pub struct IaPdBuilder {
+    time_1: u32,
+    time_2: u32,
+    options: Vec<DHCPv6Option<'static>>,
+}
+
+impl IaPdBuilder {
+    pub fn times(mut self, time_1: u32, time_2: u32) -> Self {
+        self.time_1 = time_1;
+        self.time_2 = time_2;
+        self
+    }
+
+    pub fn option(mut self, option: &DHCPv6Option) -> Self {
+        self.options.push(option.clone().into_owned());
+        self
+    }
+
+    pub fn prefix(
+        mut self,
+        prefix: Ipv6Addr,
+        prefix_length: u8,
+        prefered_lifetime: u32,
+        valid_lifetime: u32,
+    ) -> Self {
+        self.options.push(DHCPv6Option::IdentityAssociationPrefix {
+            prefered_lifetime,
+            valid_lifetime,
+            prefix_length,
+            prefix,
+            options: Vec::new(),
+        });
+        self
+    }
+
+    pub fn status(mut self, code: StatusCode, message: &str) -> Self {
+        self.options.push(ia_status(code, message));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::header::{parse_message, DHCPv6Header};
+    use crate::structs::interface_id::InterfaceId;
+    use crate::structs::link_layer::MacAddr;
+    use crate::structs::relay::parse_relay_chain;
+
+    fn duid() -> Duid<'static> {
+        Duid::LinkLayer {
+            hardware_type: 1,
+            link_layer_address: Cow::Borrowed(b"\x00\x11\x22\xaa\xbb\xcc"),
+        }
+    }
+
+    #[test]
+    fn test_build_solicit() {
+        let bytes = MessageBuilder::solicit(0x123456)
+            .client_id(&duid())
+            .ia_na(1, |ia| ia.times(3600, 5400))
+            .oro(&[23, 24])
+            .elapsed(0)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            parse_message(&bytes).unwrap(),
+            DHCPv6Header::ClientServer {
+                message_type: DHCPv6MessageType::Solicit,
+                transaction_id: 0x123456,
+                options: vec![
+                    DHCPv6Option::CliendID { duid: duid() },
+                    DHCPv6Option::IdentityAssociationForNonTemporaryAddresses {
+                        id: 1,
+                        time_1: 3600,
+                        time_2: 5400,
+                        options: vec![],
+                    },
+                    DHCPv6Option::OptionRequest {
+                        options: vec![23, 24],
+                    },
+                    DHCPv6Option::ElapstedTime { elapsed_time: 0 },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_build_reply_with_leases() {
+        let address = "2001:db8::1".parse().unwrap();
+        let prefix = "2001:db8:1::".parse().unwrap();
+        let bytes = MessageBuilder::reply(7)
+            .server_id(&duid())
+            .ia_na(1, |ia| ia.times(100, 200).address(address, 300, 400))
+            .ia_pd(2, |ia| {
+                ia.prefix(prefix, 48, 300, 400)
+                    .status(StatusCode::Success, "ok")
+            })
+            .rapid_commit()
+            .build()
+            .unwrap();
+
+        let header = parse_message(&bytes).unwrap();
+        let options = match &header {
+            DHCPv6Header::ClientServer { options, .. } => options,
+            _ => panic!("unexpected header {:?}", header),
+        };
+        assert_eq!(options.len(), 4);
+        assert_eq!(options[1].addresses().count(), 1);
+        assert_eq!(options[2].prefixes().count(), 1);
+        assert_eq!(
+            options[2].status(),
+            Some((StatusCode::Success, "ok".into()))
+        );
+    }
+
+    #[test]
+    fn test_build_ia_ta() {
+        let address = "2001:db8::1".parse().unwrap();
+        let bytes = MessageBuilder::reply(7)
+            .ia_ta(3, |ia| ia.address(address, 300, 400))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            parse_message(&bytes).unwrap().options(),
+            &[DHCPv6Option::IdentityAssociationForTemporaryAddresses {
+                id: 3,
+                options: vec![DHCPv6Option::IdentityAssociationAddress {
+                    address,
+                    prefered_lifetime: 300,
+                    valid_lifetime: 400,
+                    options: vec![],
+                }],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_build_relay_forward() {
+        let inner = MessageBuilder::solicit(1).elapsed(0).build().unwrap();
+        let bytes =
+            MessageBuilder::relay_forward(0, Ipv6Addr::UNSPECIFIED, "fe80::1".parse().unwrap())
+                .interface_id(b"eth0")
+                .relay_message(&inner)
+                .build()
+                .unwrap();
+
+        let chain = parse_relay_chain(&bytes).unwrap().1;
+        assert_eq!(chain.hops.len(), 1);
+        assert_eq!(chain.hops[0].interface_id(), Some(&b"eth0"[..]));
+        assert_eq!(
+            chain.message,
+            DHCPv6Header::ClientServer {
+                message_type: DHCPv6MessageType::Solicit,
+                transaction_id: 1,
+                options: vec![DHCPv6Option::ElapstedTime { elapsed_time: 0 }],
+            }
+        );
+    }
+
+    #[test]
+    fn test_build_relay_forward_subscriber_options() {
+        let inner = MessageBuilder::solicit(1).elapsed(0).build().unwrap();
+        let bytes =
+            MessageBuilder::relay_forward(0, Ipv6Addr::UNSPECIFIED, "fe80::1".parse().unwrap())
+                .interface_id(b"dslam-7 atm 1/3:8.35")
+                .remote_id(3561, b"line-42")
+                .subscriber_id(b"customer-1")
+                .client_link_layer_address(HardwareType::Ethernet, b"\x00\x11\x22\xaa\xbb\xcc")
+                .relay_message(&inner)
+                .build()
+                .unwrap();
+
+        let header = parse_message(&bytes).unwrap();
+        assert_eq!(header.remote_id(), Some((3561, &b"line-42"[..])));
+        assert_eq!(header.subscriber_id(), Some(&b"customer-1"[..]));
+
+        let chain = parse_relay_chain(&bytes).unwrap().1;
+        assert_eq!(chain.hops[0].remote_id(), header.remote_id());
+        assert_eq!(chain.hops[0].subscriber_id(), header.subscriber_id());
+        assert_eq!(
+            chain.hops[0].client_mac_address(),
+            Some(MacAddr([0x00, 0x11, 0x22, 0xaa, 0xbb, 0xcc]))
+        );
+        let (hardware_type, address) = header.client_link_layer_address().unwrap();
+        assert_eq!(
+            duid().matches_link_layer_address(hardware_type, address),
+            Some(true)
+        );
+        match InterfaceId::decode(chain.hops[0].interface_id().unwrap()) {
+            InterfaceId::CircuitId(circuit_id) => assert_eq!(circuit_id.access_node, "dslam-7"),
+            interface_id => panic!("unexpected interface id {:?}", interface_id),
+        }
+    }
+
+    #[test]
+    fn test_build_errors() {
+        assert_eq!(
+            MessageBuilder::solicit(0x0100_0000).build(),
+            Err(EncodeError::TransactionIdOutOfRange(0x0100_0000))
+        );
+        assert_eq!(
+            MessageBuilder::solicit(1)
+                .relay_message(&[0u8; 0x10000])
+                .build(),
+            Err(EncodeError::OptionTooLong {
+                code: 9,
+                length: 0x10000
+            })
+        );
+        assert_eq!(
+            MessageBuilder::reply(1)
+                .ia_pd(1, |ia| ia.prefix("2001:db8::1".parse().unwrap(), 32, 1, 2))
+                .build(),
+            Err(EncodeError::InvalidPrefix {
+                prefix: "2001:db8::1".parse().unwrap(),
+                prefix_length: 32
+            })
+        );
+    }
+
+    #[test]
+    fn test_build_unexpected_message_type() {
+        assert_eq!(
+            MessageBuilder::new(DHCPv6MessageType::RelayForw, 1).build(),
+            Err(EncodeError::UnexpectedMessageType(12))
+        );
+        assert_eq!(
+            MessageBuilder::new(DHCPv6MessageType::DHCPv4Query, 1).build(),
+            Err(EncodeError::UnexpectedMessageType(20))
+        );
+        assert_eq!(
+            MessageBuilder::new(DHCPv6MessageType::Unassigned(13), 1).build(),
+            Err(EncodeError::UnexpectedMessageType(13))
+        );
+        assert_eq!(
+            MessageBuilder::relay(
+                DHCPv6MessageType::Solicit,
+                0,
+                Ipv6Addr::UNSPECIFIED,
+                Ipv6Addr::UNSPECIFIED
+            )
+            .build(),
+            Err(EncodeError::UnexpectedMessageType(1))
+        );
+        assert!(MessageBuilder::new(DHCPv6MessageType::Leasequery, 1)
+            .build()
+            .is_ok());
+    }
+}
@@ -0,0 +1,113 @@
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum UnknownOptions {
+    Keep,
+    Reject,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TrailingBytes {
+    Ignore,
+    Reject,
+}
+
+// Keep only applies to options with a single valid length (Preference,
+// Elapsed Time, Server Unicast, ...) and to NTP server options with a bad
+// address suboption: those become DHCPv6Option::Malformed, which encodes back
+// to the exact bytes received. Encoding a Malformed option whose data is a
+// valid value for its code fails with NonCanonicalOption, so it never turns
+// into a typed option. Length errors in any other option are rejected with
+// BadOptionLength.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum LengthMismatch {
+    Keep,
+    Reject,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Utf8Policy {
+    Strict,
+    Lossy,
+}
+
+pub const HOP_COUNT_LIMIT: usize = 8;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseConfig {
+    pub unknown_options: UnknownOptions,
+    pub trailing_bytes: TrailingBytes,
+    pub length_mismatch: LengthMismatch,
+    // Limit on the options of one message, nested options included.
+    pub max_options: usize,
+    pub max_depth: usize,
+    pub max_relay_hops: usize,
+    pub status_message_utf8: Utf8Policy,
+}
+
+impl ParseConfig {
+    pub fn strict() -> Self {
+        Self {
+            unknown_options: UnknownOptions::Reject,
+            trailing_bytes: TrailingBytes::Reject,
+            length_mismatch: LengthMismatch::Reject,
+            status_message_utf8: Utf8Policy::Strict,
+            ..Self::default()
+        }
+    }
+
+    pub fn tolerant() -> Self {
+        Self {
+            unknown_options: UnknownOptions::Keep,
+            trailing_bytes: TrailingBytes::Ignore,
+            length_mismatch: LengthMismatch::Keep,
+            status_message_utf8: Utf8Policy::Lossy,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for ParseConfig {
+    fn default() -> Self {
+        Self {
+            unknown_options: UnknownOptions::Keep,
+            trailing_bytes: TrailingBytes::Reject,
+            length_mismatch: LengthMismatch::Reject,
+            max_options: 1024,
+            max_depth: 8,
+            max_relay_hops: HOP_COUNT_LIMIT,
+            status_message_utf8: Utf8Policy::Strict,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config_presets() {
+        assert_eq!(
+            ParseConfig::strict(),
+            ParseConfig {
+                unknown_options: UnknownOptions::Reject,
+                trailing_bytes: TrailingBytes::Reject,
+                length_mismatch: LengthMismatch::Reject,
+                max_options: 1024,
+                max_depth: 8,
+                max_relay_hops: HOP_COUNT_LIMIT,
+                status_message_utf8: Utf8Policy::Strict,
+            }
+        );
+        assert_eq!(
+            ParseConfig::tolerant(),
+            ParseConfig {
+                unknown_options: UnknownOptions::Keep,
+                trailing_bytes: TrailingBytes::Ignore,
+                length_mismatch: LengthMismatch::Keep,
+                max_options: 1024,
+                max_depth: 8,
+                max_relay_hops: HOP_COUNT_LIMIT,
+                status_message_utf8: Utf8Policy::Lossy,
+            }
+        );
+    }
+}
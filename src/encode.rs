@@ -0,0 +1,134 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+use core::net::Ipv6Addr;
+use core::time::Duration;
+
+pub trait Sink {
+    fn put(&mut self, data: &[u8]);
+}
+
+#[cfg(feature = "alloc")]
+impl Sink for Vec<u8> {
+    fn put(&mut self, data: &[u8]) {
+        self.extend_from_slice(data);
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) struct SliceSink<'a> {
+    buffer: &'a mut [u8],
+    position: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> SliceSink<'a> {
+    pub(crate) fn new(buffer: &'a mut [u8]) -> Self {
+        Self {
+            buffer,
+            position: 0,
+        }
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Sink for SliceSink<'_> {
+    fn put(&mut self, data: &[u8]) {
+        let end = self.position + data.len();
+        if let Some(dst) = self.buffer.get_mut(self.position..end) {
+            dst.copy_from_slice(data);
+        }
+        self.position = end;
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum EncodeError {
+    BufferTooSmall { needed: usize, available: usize },
+    OptionTooLong { code: u16, length: usize },
+    NonCanonicalOption { code: u16 },
+    NonCanonicalDuid { duid_type: u16 },
+    InvalidPrefix { prefix: Ipv6Addr, prefix_length: u8 },
+    DurationOutOfRange { code: u16, duration: Duration },
+    UnexpectedMessageType(u8),
+    TransactionIdOutOfRange(u32),
+    FlagsOutOfRange(u32),
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferTooSmall { needed, available } => write!(
+                f,
+                "buffer too small: {} bytes needed, {} available",
+                needed, available
+            ),
+            Self::OptionTooLong { code, length } => write!(
+                f,
+                "option {} is {} bytes long, which does not fit in 16 bits",
+                code, length
+            ),
+            Self::NonCanonicalOption { code } => write!(
+                f,
+                "option {} has a typed variant and cannot be encoded as unknown",
+                code
+            ),
+            Self::NonCanonicalDuid { duid_type } => write!(
+                f,
+                "DUID type {} has a typed variant and cannot be encoded as other",
+                duid_type
+            ),
+            Self::InvalidPrefix {
+                prefix,
+                prefix_length,
+            } => write!(f, "invalid prefix {}/{}", prefix, prefix_length),
+            Self::DurationOutOfRange { code, duration } => write!(
+                f,
+                "option {} cannot encode {:?} as a whole number of seconds",
+                code, duration
+            ),
+            Self::UnexpectedMessageType(message_type) => write!(
+                f,
+                "message type {} does not use this header format",
+                message_type
+            ),
+            Self::TransactionIdOutOfRange(id) => {
+                write!(f, "transaction id {:#x} does not fit in 24 bits", id)
+            }
+            Self::FlagsOutOfRange(flags) => {
+                write!(f, "flags {:#x} do not fit in 24 bits", flags)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EncodeError {}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn check_length(code: u16, length: usize) -> Result<u16, EncodeError> {
+    if length > u16::MAX as usize {
+        Err(EncodeError::OptionTooLong { code, length })
+    } else {
+        Ok(length as u16)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slice_sink_overflow() {
+        let mut buffer = [0u8; 4];
+        let mut sink = SliceSink::new(&mut buffer[..]);
+        sink.put(b"abc");
+        sink.put(b"de");
+        assert_eq!(sink.position(), 5);
+        assert_eq!(&buffer, b"abc\x00");
+    }
+}
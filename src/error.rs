@@ -0,0 +1,193 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+use nom::IResult;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ErrorKind {
+    TruncatedHeader,
+    TruncatedOption,
+    BadOptionLength { code: u16, length: u16 },
+    BadSuboptionLength { option: u16, code: u16, length: u16 },
+    InvalidUtf8,
+    InvalidDomainName,
+    InvalidPrefix { prefix_length: u8 },
+    UnknownMessageType(u8),
+    UnknownOption(u16),
+    TrailingBytes,
+    NestingTooDeep,
+    MissingRelayMessage,
+    TooManyRelayHops,
+    TooManyOptions,
+    Nom(nom::error::ErrorKind),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TruncatedHeader => f.write_str("truncated message header"),
+            Self::TruncatedOption => f.write_str("truncated option"),
+            Self::BadOptionLength { code, length } => {
+                write!(f, "bad length {} for option code {}", length, code)
+            }
+            Self::BadSuboptionLength {
+                option,
+                code,
+                length,
+            } => write!(
+                f,
+                "bad length {} for suboption code {} of option code {}",
+                length, code, option
+            ),
+            Self::InvalidUtf8 => f.write_str("invalid UTF-8 string"),
+            Self::InvalidDomainName => f.write_str("invalid domain name"),
+            Self::InvalidPrefix { prefix_length } => {
+                write!(f, "invalid prefix of length {}", prefix_length)
+            }
+            Self::UnknownMessageType(message_type) => {
+                write!(f, "unknown message type {}", message_type)
+            }
+            Self::UnknownOption(code) => write!(f, "unknown option code {}", code),
+            Self::TrailingBytes => f.write_str("trailing bytes after last option"),
+            Self::NestingTooDeep => f.write_str("options nested too deep"),
+            Self::MissingRelayMessage => f.write_str("relay message option missing"),
+            Self::TooManyRelayHops => f.write_str("too many relay hops"),
+            Self::TooManyOptions => f.write_str("too many options"),
+            Self::Nom(kind) => write!(f, "parser error ({})", kind.description()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError<I> {
+    pub input: I,
+    pub kind: ErrorKind,
+    #[cfg(feature = "alloc")]
+    pub path: Vec<u16>,
+}
+
+impl<I> ParseError<I> {
+    pub fn new(input: I, kind: ErrorKind) -> Self {
+        Self {
+            input,
+            kind,
+            #[cfg(feature = "alloc")]
+            path: Vec::new(),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn in_option(mut self, code: u16) -> Self {
+        self.path.insert(0, code);
+        self
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub(crate) fn in_option(self, _code: u16) -> Self {
+        self
+    }
+}
+
+impl<I> nom::error::ParseError<I> for ParseError<I> {
+    fn from_error_kind(input: I, kind: nom::error::ErrorKind) -> Self {
+        Self::new(input, ErrorKind::Nom(kind))
+    }
+
+    fn append(_input: I, _kind: nom::error::ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+pub type ParseResult<'a, T> = IResult<&'a [u8], T, ParseError<&'a [u8]>>;
+
+pub(crate) fn fail<T>(input: &[u8], kind: ErrorKind) -> ParseResult<'_, T> {
+    Err(nom::Err::Error(ParseError::new(input, kind)))
+}
+
+pub(crate) fn with_kind<'a, O, F>(kind: ErrorKind, f: F) -> impl Fn(&'a [u8]) -> ParseResult<'a, O>
+where
+    F: Fn(&'a [u8]) -> ParseResult<'a, O>,
+{
+    move |input: &'a [u8]| {
+        f(input).map_err(|e| {
+            e.map(|mut e| {
+                e.kind = kind.clone();
+                e
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub offset: usize,
+    #[cfg(feature = "alloc")]
+    pub path: Vec<u16>,
+}
+
+impl Error {
+    pub fn from_parse_error(base: &[u8], error: ParseError<&[u8]>) -> Self {
+        let offset = (error.input.as_ptr() as usize)
+            .checked_sub(base.as_ptr() as usize)
+            .filter(|offset| *offset <= base.len())
+            .unwrap_or(0);
+
+        Self {
+            kind: error.kind,
+            offset,
+            #[cfg(feature = "alloc")]
+            path: error.path,
+        }
+    }
+
+    pub fn from_nom_error(base: &[u8], error: nom::Err<ParseError<&[u8]>>) -> Self {
+        match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => Self::from_parse_error(base, e),
+            nom::Err::Incomplete(_) => Self {
+                kind: ErrorKind::TruncatedHeader,
+                offset: base.len(),
+                #[cfg(feature = "alloc")]
+                path: Vec::new(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.kind, self.offset)?;
+        #[cfg(feature = "alloc")]
+        if let Some((first, others)) = self.path.split_first() {
+            write!(f, " (in option {}", first)?;
+            for code in others {
+                write!(f, " > {}", code)?;
+            }
+            f.write_str(")")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_offset_and_display() {
+        let input = b"\x00\x01\x02\x03";
+        let mut error = ParseError::new(&input[2..], ErrorKind::TruncatedOption);
+        error.path = vec![3, 5];
+
+        let error = Error::from_parse_error(&input[..], error);
+        assert_eq!(error.offset, 2);
+        assert_eq!(
+            error.to_string(),
+            "truncated option at offset 2 (in option 3 > 5)"
+        );
+    }
+}
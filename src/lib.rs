@@ -1,6 +1,17 @@
-#[macro_use]
-extern crate enum_primitive_derive;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
 mod utils;
 
+#[cfg(feature = "alloc")]
+pub mod builder;
+pub mod config;
+pub mod encode;
+pub mod error;
 pub mod structs;
+#[cfg(feature = "alloc")]
+pub mod validate;
 pub use structs::*;
@@ -0,0 +1,372 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+use nom::bytes::complete::take;
+use nom::number::complete::be_u8;
+
+use crate::encode::Sink;
+use crate::error::{fail, ErrorKind, ParseResult};
+
+pub const MAX_LABEL_LENGTH: usize = 63;
+pub const MAX_NAME_LENGTH: usize = 255;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct DomainName<'a> {
+    raw: Cow<'a, [u8]>,
+    fully_qualified: bool,
+}
+
+impl<'a> DomainName<'a> {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.raw
+    }
+
+    pub fn labels(&self) -> Labels<'_> {
+        Labels { input: &self.raw }
+    }
+
+    pub fn is_fully_qualified(&self) -> bool {
+        self.fully_qualified
+    }
+
+    pub fn into_owned(self) -> DomainName<'static> {
+        DomainName {
+            raw: Cow::Owned(self.raw.into_owned()),
+            fully_qualified: self.fully_qualified,
+        }
+    }
+
+    pub fn as_borrowed(&self) -> DomainName<'_> {
+        DomainName {
+            raw: Cow::Borrowed(&self.raw),
+            fully_qualified: self.fully_qualified,
+        }
+    }
+}
+
+impl DomainName<'static> {
+    // A fully qualified name is terminated by the root label, RFC 1035
+    // section 3.1. Partial names, see RFC 4704 section 4.2, are not.
+    pub fn from_labels<I>(labels: I, fully_qualified: bool) -> Result<Self, ParseDomainNameError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let mut raw = Vec::new();
+        for label in labels {
+            let label = label.as_ref();
+            if label.is_empty() || label.len() > MAX_LABEL_LENGTH {
+                return Err(ParseDomainNameError);
+            }
+            raw.push(label.len() as u8);
+            raw.extend_from_slice(label);
+        }
+        if fully_qualified {
+            raw.push(0);
+        }
+        if raw.len() > MAX_NAME_LENGTH {
+            return Err(ParseDomainNameError);
+        }
+
+        Ok(DomainName {
+            raw: Cow::Owned(raw),
+            fully_qualified,
+        })
+    }
+}
+
+pub struct Labels<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> Iterator for Labels<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (len, rest) = self.input.split_first()?;
+        if *len == 0 {
+            self.input = &[];
+            return None;
+        }
+        let (label, rest) = rest.split_at(*len as usize);
+        self.input = rest;
+
+        Some(label)
+    }
+}
+
+impl fmt::Display for DomainName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, label) in self.labels().enumerate() {
+            if i != 0 {
+                f.write_str(".")?;
+            }
+            for byte in label {
+                match byte {
+                    b'.' | b'\\' => write!(f, "\\{}", *byte as char)?,
+                    0x21..=0x7e => write!(f, "{}", *byte as char)?,
+                    _ => write!(f, "\\{:03}", byte)?,
+                }
+            }
+        }
+
+        if self.fully_qualified {
+            f.write_str(".")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ParseDomainNameError;
+
+impl fmt::Display for ParseDomainNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid domain name")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseDomainNameError {}
+
+// Accepts the presentation format produced by Display: a trailing dot marks a
+// fully qualified name, and \., \\ and \DDD escape label bytes.
+impl FromStr for DomainName<'static> {
+    type Err = ParseDomainNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "." {
+            return DomainName::from_labels(Vec::<Vec<u8>>::new(), true);
+        }
+
+        let mut labels = Vec::new();
+        let mut label = Vec::new();
+        let mut bytes = s.bytes();
+        let mut fully_qualified = false;
+
+        while let Some(byte) = bytes.next() {
+            match byte {
+                b'.' => {
+                    labels.push(core::mem::take(&mut label));
+                    fully_qualified = bytes.len() == 0;
+                }
+                b'\\' => match bytes.next() {
+                    Some(digit @ b'0'..=b'9') => {
+                        let mut value = u32::from(digit - b'0');
+                        for _ in 0..2 {
+                            match bytes.next() {
+                                Some(digit @ b'0'..=b'9') => {
+                                    value = value * 10 + u32::from(digit - b'0')
+                                }
+                                _ => return Err(ParseDomainNameError),
+                            }
+                        }
+                        label.push(u8::try_from(value).map_err(|_| ParseDomainNameError)?);
+                    }
+                    Some(escaped @ 0x21..=0x7e) => label.push(escaped),
+                    _ => return Err(ParseDomainNameError),
+                },
+                0x21..=0x7e => label.push(byte),
+                _ => return Err(ParseDomainNameError),
+            }
+        }
+        if !label.is_empty() {
+            labels.push(label);
+        }
+
+        DomainName::from_labels(labels, fully_qualified)
+    }
+}
+
+pub fn parse_domain_name(input: &[u8]) -> ParseResult<'_, DomainName<'_>> {
+    let mut rest = input;
+    let mut fully_qualified = false;
+
+    while !rest.is_empty() {
+        let (next, len) = be_u8(rest)?;
+        if len == 0 {
+            rest = next;
+            fully_qualified = true;
+            break;
+        }
+        if len as usize > MAX_LABEL_LENGTH {
+            return fail(rest, ErrorKind::InvalidDomainName);
+        }
+        let (next, _) = take(len as usize)(next)?;
+        rest = next;
+    }
+
+    let len = input.len() - rest.len();
+    if len > MAX_NAME_LENGTH {
+        return fail(input, ErrorKind::InvalidDomainName);
+    }
+
+    Ok((
+        rest,
+        DomainName {
+            raw: Cow::Borrowed(&input[..len]),
+            fully_qualified,
+        },
+    ))
+}
+
+pub fn parse_domain_name_list(input: &[u8]) -> ParseResult<'_, Vec<DomainName<'_>>> {
+    let mut names = Vec::new();
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        let (next, name) = parse_domain_name(rest)?;
+        if !name.is_fully_qualified() {
+            return fail(rest, ErrorKind::InvalidDomainName);
+        }
+        names.push(name);
+        rest = next;
+    }
+
+    Ok((rest, names))
+}
+
+pub fn domain_name_len(name: &DomainName) -> usize {
+    name.raw.len()
+}
+
+pub fn domain_name_list_len(names: &[DomainName]) -> usize {
+    names.iter().map(domain_name_len).sum()
+}
+
+pub fn encode_domain_name<S: Sink>(name: &DomainName, output: &mut S) {
+    output.put(&name.raw);
+}
+
+pub fn encode_domain_name_list<S: Sink>(names: &[DomainName], output: &mut S) {
+    for name in names {
+        encode_domain_name(name, output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_domain_name() {
+        let input = b"\x07example\x03com\x00";
+        let (rest, name) = parse_domain_name(&input[..]).unwrap();
+        assert!(rest.is_empty());
+        assert!(name.is_fully_qualified());
+        assert_eq!(
+            name.labels().collect::<Vec<_>>(),
+            vec![&b"example"[..], &b"com"[..]]
+        );
+        assert_eq!(name.to_string(), "example.com.");
+        assert_eq!(domain_name_len(&name), input.len());
+
+        let mut output = Vec::new();
+        encode_domain_name(&name, &mut output);
+        assert_eq!(&output[..], &input[..]);
+    }
+
+    #[test]
+    fn test_parse_partial_domain_name() {
+        let input = b"\x04host";
+        let (rest, name) = parse_domain_name(&input[..]).unwrap();
+        assert!(rest.is_empty());
+        assert!(!name.is_fully_qualified());
+        assert_eq!(name.to_string(), "host");
+
+        let (_, name) = parse_domain_name(&b"\x04hos\x00"[..]).unwrap();
+        assert!(!name.is_fully_qualified());
+        assert_eq!(name.labels().collect::<Vec<_>>(), vec![&b"hos\x00"[..]]);
+        assert!(parse_domain_name_list(&b"\x04hos\x00"[..]).is_err());
+
+        let (_, root) = parse_domain_name(&b"\x00"[..]).unwrap();
+        assert!(root.is_fully_qualified());
+        assert_eq!(root.labels().count(), 0);
+        assert_eq!(root.to_string(), ".");
+    }
+
+    #[test]
+    fn test_domain_name_display_escapes() {
+        let (_, name) = parse_domain_name(&b"\x04a.b\\\x02\x01 \x00"[..]).unwrap();
+        assert_eq!(name.to_string(), "a\\.b\\\\.\\001\\032.");
+        assert_eq!(name.to_string().parse(), Ok(name.into_owned()));
+    }
+
+    #[test]
+    fn test_domain_name_from_labels() {
+        let name = DomainName::from_labels(&["example", "com"], true).unwrap();
+        assert_eq!(name.as_bytes(), b"\x07example\x03com\x00");
+        assert!(name.is_fully_qualified());
+        assert_eq!("example.com.".parse(), Ok(name));
+
+        let name = DomainName::from_labels(&["host"], false).unwrap();
+        assert_eq!(name.as_bytes(), b"\x04host");
+        assert!(!name.is_fully_qualified());
+        assert_eq!("host".parse(), Ok(name));
+
+        let root = DomainName::from_labels(Vec::<&str>::new(), true).unwrap();
+        assert_eq!(root.as_bytes(), b"\x00");
+        assert_eq!(".".parse(), Ok(root));
+
+        let label = [b'a'; 64];
+        assert_eq!(
+            DomainName::from_labels(&[&label[..]], true),
+            Err(ParseDomainNameError)
+        );
+        assert_eq!(
+            DomainName::from_labels(&[&label[..63]; 4], true),
+            Err(ParseDomainNameError)
+        );
+        assert!(DomainName::from_labels(&[&label[..63]; 3], true).is_ok());
+        assert_eq!(
+            DomainName::from_labels(&["example", ""], true),
+            Err(ParseDomainNameError)
+        );
+
+        for text in &[
+            "example..com",
+            ".com",
+            "exa mple",
+            "\\25",
+            "\\256",
+            "caf\u{e9}",
+        ] {
+            assert_eq!(text.parse::<DomainName>(), Err(ParseDomainNameError));
+        }
+    }
+
+    #[test]
+    fn test_parse_domain_name_list() {
+        let input = b"\x07example\x03com\x00\x03lan\x00";
+        let (rest, names) = parse_domain_name_list(&input[..]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            names.iter().map(|n| n.to_string()).collect::<Vec<_>>(),
+            vec!["example.com.", "lan."]
+        );
+        assert_eq!(domain_name_list_len(&names), input.len());
+
+        let mut output = Vec::new();
+        encode_domain_name_list(&names, &mut output);
+        assert_eq!(&output[..], &input[..]);
+    }
+
+    #[test]
+    fn test_invalid_domain_names() {
+        assert!(parse_domain_name(&b"\xc0\x0c"[..]).is_err());
+        assert!(parse_domain_name(&b"\x40toolong"[..]).is_err());
+        assert!(parse_domain_name(&b"\x07exam"[..]).is_err());
+        assert!(parse_domain_name_list(&b"\x03lan\x00\x04host"[..]).is_err());
+
+        let mut long = Vec::new();
+        for _ in 0..5 {
+            long.push(63u8);
+            long.extend_from_slice(&[b'a'; 63]);
+        }
+        long.push(0);
+        assert!(parse_domain_name(&long[..]).is_err());
+    }
+}
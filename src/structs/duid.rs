@@ -0,0 +1,491 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+use nom::bytes::complete::take;
+use nom::combinator::rest;
+use nom::number::complete::{be_u16, be_u32};
+use nom::sequence::tuple;
+#[cfg(feature = "std")]
+use std::time::{Duration, SystemTime};
+
+use crate::encode::{EncodeError, Sink};
+use crate::error::ParseResult;
+use crate::structs::link_layer::{HardwareType, MacAddr};
+
+#[cfg(feature = "std")]
+const DUID_TIME_EPOCH: u64 = 946_684_800;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Duid<'a> {
+    LinkLayerTime {
+        hardware_type: u16,
+        time: u32,
+        link_layer_address: Cow<'a, [u8]>,
+    },
+    Enterprise {
+        enterprise_number: u32,
+        identifier: Cow<'a, [u8]>,
+    },
+    LinkLayer {
+        hardware_type: u16,
+        link_layer_address: Cow<'a, [u8]>,
+    },
+    Uuid([u8; 16]),
+    Other {
+        duid_type: u16,
+        data: Cow<'a, [u8]>,
+    },
+}
+
+impl<'a> Duid<'a> {
+    pub fn duid_type(&self) -> u16 {
+        match self {
+            Duid::LinkLayerTime { .. } => 1,
+            Duid::Enterprise { .. } => 2,
+            Duid::LinkLayer { .. } => 3,
+            Duid::Uuid(_) => 4,
+            Duid::Other { duid_type, .. } => *duid_type,
+        }
+    }
+
+    pub fn link_layer_address(&self) -> Option<(u16, &[u8])> {
+        match self {
+            Duid::LinkLayerTime {
+                hardware_type,
+                link_layer_address,
+                ..
+            }
+            | Duid::LinkLayer {
+                hardware_type,
+                link_layer_address,
+            } => Some((*hardware_type, link_layer_address)),
+            _ => None,
+        }
+    }
+
+    pub fn mac_address(&self) -> Option<MacAddr> {
+        let (hardware_type, address) = self.link_layer_address()?;
+
+        MacAddr::from_link_layer(HardwareType::from_u16(hardware_type), address)
+    }
+
+    // Returns None when the DUID does not carry a link-layer address and
+    // therefore cannot be compared, see RFC 6939 section 4.
+    pub fn matches_link_layer_address(
+        &self,
+        hardware_type: HardwareType,
+        address: &[u8],
+    ) -> Option<bool> {
+        let (duid_hardware_type, duid_address) = self.link_layer_address()?;
+
+        Some(duid_hardware_type == hardware_type.to_u16() && duid_address == address)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn timestamp(&self) -> Option<SystemTime> {
+        match self {
+            Duid::LinkLayerTime { time, .. } => Some(
+                SystemTime::UNIX_EPOCH + Duration::from_secs(DUID_TIME_EPOCH + u64::from(*time)),
+            ),
+            _ => None,
+        }
+    }
+
+    pub fn into_owned(self) -> Duid<'static> {
+        match self {
+            Duid::LinkLayerTime {
+                hardware_type,
+                time,
+                link_layer_address,
+            } => Duid::LinkLayerTime {
+                hardware_type,
+                time,
+                link_layer_address: Cow::Owned(link_layer_address.into_owned()),
+            },
+            Duid::Enterprise {
+                enterprise_number,
+                identifier,
+            } => Duid::Enterprise {
+                enterprise_number,
+                identifier: Cow::Owned(identifier.into_owned()),
+            },
+            Duid::LinkLayer {
+                hardware_type,
+                link_layer_address,
+            } => Duid::LinkLayer {
+                hardware_type,
+                link_layer_address: Cow::Owned(link_layer_address.into_owned()),
+            },
+            Duid::Uuid(uuid) => Duid::Uuid(uuid),
+            Duid::Other { duid_type, data } => Duid::Other {
+                duid_type,
+                data: Cow::Owned(data.into_owned()),
+            },
+        }
+    }
+
+    pub fn as_borrowed(&self) -> Duid<'_> {
+        match self {
+            Duid::LinkLayerTime {
+                hardware_type,
+                time,
+                link_layer_address,
+            } => Duid::LinkLayerTime {
+                hardware_type: *hardware_type,
+                time: *time,
+                link_layer_address: Cow::Borrowed(link_layer_address),
+            },
+            Duid::Enterprise {
+                enterprise_number,
+                identifier,
+            } => Duid::Enterprise {
+                enterprise_number: *enterprise_number,
+                identifier: Cow::Borrowed(identifier),
+            },
+            Duid::LinkLayer {
+                hardware_type,
+                link_layer_address,
+            } => Duid::LinkLayer {
+                hardware_type: *hardware_type,
+                link_layer_address: Cow::Borrowed(link_layer_address),
+            },
+            Duid::Uuid(uuid) => Duid::Uuid(*uuid),
+            Duid::Other { duid_type, data } => Duid::Other {
+                duid_type: *duid_type,
+                data: Cow::Borrowed(data),
+            },
+        }
+    }
+}
+
+// RFC 8415 sections 11.2 to 11.5. A known DUID type whose body does not fit
+// its layout is kept as Duid::Other rather than failing the whole option.
+fn has_typed_layout(duid_type: u16, len: usize) -> bool {
+    match duid_type {
+        1 => len >= 6,
+        2 => len >= 4,
+        3 => len >= 2,
+        4 => len == 16,
+        _ => false,
+    }
+}
+
+pub fn parse_duid(input: &[u8]) -> ParseResult<'_, Duid<'_>> {
+    let (input, duid_type) = be_u16(input)?;
+
+    match (duid_type, has_typed_layout(duid_type, input.len())) {
+        (1, true) => {
+            let (rest, (hardware_type, time, link_layer_address)) =
+                tuple((be_u16, be_u32, rest))(input)?;
+            Ok((
+                rest,
+                Duid::LinkLayerTime {
+                    hardware_type,
+                    time,
+                    link_layer_address: Cow::Borrowed(link_layer_address),
+                },
+            ))
+        }
+        (2, true) => {
+            let (rest, (enterprise_number, identifier)) = tuple((be_u32, rest))(input)?;
+            Ok((
+                rest,
+                Duid::Enterprise {
+                    enterprise_number,
+                    identifier: Cow::Borrowed(identifier),
+                },
+            ))
+        }
+        (3, true) => {
+            let (rest, (hardware_type, link_layer_address)) = tuple((be_u16, rest))(input)?;
+            Ok((
+                rest,
+                Duid::LinkLayer {
+                    hardware_type,
+                    link_layer_address: Cow::Borrowed(link_layer_address),
+                },
+            ))
+        }
+        (4, true) => {
+            let (rest, uuid) = take(16usize)(input)?;
+            let mut bytes = [0u8; 16];
+            bytes.copy_from_slice(uuid);
+            Ok((rest, Duid::Uuid(bytes)))
+        }
+        _ => {
+            let (rest, data) = rest(input)?;
+            Ok((
+                rest,
+                Duid::Other {
+                    duid_type,
+                    data: Cow::Borrowed(data),
+                },
+            ))
+        }
+    }
+}
+
+pub fn duid_len(duid: &Duid) -> usize {
+    2 + match duid {
+        Duid::LinkLayerTime {
+            link_layer_address, ..
+        } => 6 + link_layer_address.len(),
+        Duid::Enterprise { identifier, .. } => 4 + identifier.len(),
+        Duid::LinkLayer {
+            link_layer_address, ..
+        } => 2 + link_layer_address.len(),
+        Duid::Uuid(_) => 16,
+        Duid::Other { data, .. } => data.len(),
+    }
+}
+
+// Duid::Other with a body that fits a known layout would parse back as the
+// typed variant, so it is rejected like Unknown options shadowing typed codes.
+pub fn encode_duid<S: Sink>(duid: &Duid, output: &mut S) -> Result<(), EncodeError> {
+    if let Duid::Other { duid_type, data } = duid {
+        if has_typed_layout(*duid_type, data.len()) {
+            return Err(EncodeError::NonCanonicalDuid {
+                duid_type: *duid_type,
+            });
+        }
+    }
+
+    put_duid(duid, output);
+    Ok(())
+}
+
+fn put_duid<S: Sink>(duid: &Duid, output: &mut S) {
+    output.put(&duid.duid_type().to_be_bytes());
+
+    match duid {
+        Duid::LinkLayerTime {
+            hardware_type,
+            time,
+            link_layer_address,
+        } => {
+            output.put(&hardware_type.to_be_bytes());
+            output.put(&time.to_be_bytes());
+            output.put(link_layer_address);
+        }
+        Duid::Enterprise {
+            enterprise_number,
+            identifier,
+        } => {
+            output.put(&enterprise_number.to_be_bytes());
+            output.put(identifier);
+        }
+        Duid::LinkLayer {
+            hardware_type,
+            link_layer_address,
+        } => {
+            output.put(&hardware_type.to_be_bytes());
+            output.put(link_layer_address);
+        }
+        Duid::Uuid(uuid) => output.put(uuid),
+        Duid::Other { data, .. } => output.put(data),
+    }
+}
+
+impl fmt::Display for Duid<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bytes = Vec::with_capacity(duid_len(self));
+        put_duid(self, &mut bytes);
+
+        for (i, byte) in bytes.iter().enumerate() {
+            if i != 0 {
+                f.write_str(":")?;
+            }
+            write!(f, "{:02x}", byte)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ParseDuidError;
+
+impl fmt::Display for ParseDuidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid DUID")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseDuidError {}
+
+impl FromStr for Duid<'static> {
+    type Err = ParseDuidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = s
+            .split(':')
+            .map(|byte| {
+                if byte.len() == 2 && byte.bytes().all(|b| b.is_ascii_hexdigit()) {
+                    u8::from_str_radix(byte, 16).map_err(|_| ParseDuidError)
+                } else {
+                    Err(ParseDuidError)
+                }
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+
+        match parse_duid(&bytes[..]) {
+            Ok((&[], duid)) => Ok(duid.into_owned()),
+            _ => Err(ParseDuidError),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    fn roundtrip(input: &[u8], expected: Duid) {
+        assert_eq!(parse_duid(input), Ok((&b""[..], expected.clone())));
+        assert_eq!(duid_len(&expected), input.len());
+
+        let mut output = Vec::new();
+        encode_duid(&expected, &mut output).unwrap();
+        assert_eq!(&output[..], input);
+    }
+
+    #[test]
+    fn test_duid_llt() {
+        let input = b"\x00\x01\x00\x01\x26\xaa\x2b\x4c\x00\x11\x22\xaa\xbb\xcc";
+        let duid = Duid::LinkLayerTime {
+            hardware_type: 1,
+            time: 0x26aa2b4c,
+            link_layer_address: Cow::Borrowed(&b"\x00\x11\x22\xaa\xbb\xcc"[..]),
+        };
+        roundtrip(&input[..], duid.clone());
+
+        assert_eq!(
+            duid.mac_address(),
+            Some(MacAddr([0x00, 0x11, 0x22, 0xaa, 0xbb, 0xcc]))
+        );
+        assert_eq!(
+            duid.timestamp(),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(946_684_800 + 0x26aa2b4c))
+        );
+    }
+
+    #[test]
+    fn test_duid_en() {
+        let input = b"\x00\x02\x00\x00\x00\x09toto";
+        let duid = Duid::Enterprise {
+            enterprise_number: 9,
+            identifier: Cow::Borrowed(&b"toto"[..]),
+        };
+        roundtrip(&input[..], duid.clone());
+        assert_eq!(duid.mac_address(), None);
+        assert_eq!(duid.timestamp(), None);
+        assert_eq!(
+            duid.matches_link_layer_address(HardwareType::Ethernet, b"toto"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_duid_ll() {
+        let input = b"\x00\x03\x00\x01\x00\x11\x22\xaa\xbb\xcc";
+        let duid = Duid::LinkLayer {
+            hardware_type: 1,
+            link_layer_address: Cow::Borrowed(&b"\x00\x11\x22\xaa\xbb\xcc"[..]),
+        };
+        roundtrip(&input[..], duid.clone());
+        assert_eq!(
+            duid.mac_address(),
+            Some(MacAddr([0x00, 0x11, 0x22, 0xaa, 0xbb, 0xcc]))
+        );
+        assert_eq!(
+            duid.matches_link_layer_address(HardwareType::Ethernet, b"\x00\x11\x22\xaa\xbb\xcc"),
+            Some(true)
+        );
+        assert_eq!(
+            duid.matches_link_layer_address(HardwareType::Ethernet, b"\x00\x11\x22\xaa\xbb\xcd"),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_duid_uuid() {
+        let mut input = b"\x00\x04".to_vec();
+        input.extend(0u8..16);
+        let mut uuid = [0u8; 16];
+        uuid.iter_mut().zip(0u8..).for_each(|(b, i)| *b = i);
+        roundtrip(&input[..], Duid::Uuid(uuid));
+    }
+
+    #[test]
+    fn test_duid_other() {
+        let input = b"\x13\x37toto";
+        roundtrip(
+            &input[..],
+            Duid::Other {
+                duid_type: 0x1337,
+                data: Cow::Borrowed(&b"toto"[..]),
+            },
+        );
+    }
+
+    #[test]
+    fn test_invalid_duid() {
+        assert!(parse_duid(&b"\x00"[..]).is_err());
+
+        // Known types with a body that does not fit their layout.
+        for input in [
+            &b"\x00\x01\x00\x01\x00"[..],
+            &b"\x00\x02\x00\x00"[..],
+            &b"\x00\x03\x00"[..],
+            &b"\x00\x04\x00\x01\x02"[..],
+        ]
+        .iter()
+        {
+            roundtrip(
+                input,
+                Duid::Other {
+                    duid_type: u16::from(input[1]),
+                    data: Cow::Borrowed(&input[2..]),
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_non_canonical_duid() {
+        let duid = Duid::Other {
+            duid_type: 3,
+            data: Cow::Borrowed(&b"\x00\x01\x00\x11\x22\xaa\xbb\xcc"[..]),
+        };
+        assert_eq!(
+            encode_duid(&duid, &mut Vec::new()),
+            Err(EncodeError::NonCanonicalDuid { duid_type: 3 })
+        );
+    }
+
+    #[test]
+    fn test_duid_display_from_str() {
+        let duid = Duid::LinkLayer {
+            hardware_type: 1,
+            link_layer_address: Cow::Borrowed(&b"\x00\x11\x22\xaa\xbb\xcc"[..]),
+        };
+        let text = "00:03:00:01:00:11:22:aa:bb:cc";
+        assert_eq!(duid.to_string(), text);
+        assert_eq!(text.parse::<Duid>(), Ok(duid.into_owned()));
+
+        assert_eq!("00:03:0".parse::<Duid>(), Err(ParseDuidError));
+        assert_eq!(
+            "00:04:00".parse::<Duid>(),
+            Ok(Duid::Other {
+                duid_type: 4,
+                data: Cow::Borrowed(&b"\x00"[..])
+            })
+        );
+        assert_eq!("zz:01".parse::<Duid>(), Err(ParseDuidError));
+        assert_eq!(
+            "00:03:00:01:+0:11:22:aa:bb:cc".parse::<Duid>(),
+            Err(ParseDuidError)
+        );
+    }
+}
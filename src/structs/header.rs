@@ -1,11 +1,25 @@
-use crate::structs::message_types::{parse_dhcpv6_message_type, DHCPv6MessageType};
-use crate::structs::options::{parse_dhcpv6_options, DHCPv6Option};
+use crate::config::ParseConfig;
+use crate::encode::{EncodeError, Sink, SliceSink};
+use crate::error::{with_kind, Error, ErrorKind, ParseResult};
+use crate::structs::link_layer::{HardwareType, MacAddr};
+use crate::structs::message_types::{
+    encode_dhcpv6_message_type, parse_dhcpv6_message_type, DHCPv6MessageType,
+};
+use crate::structs::option_codes::OptionCode;
+use crate::structs::options::{
+    dhcpv6_options_len, encode_dhcpv6_options, find_client_link_layer_address, find_interface_id,
+    find_remote_id, find_subscriber_id, options_as_borrowed, options_into_owned,
+    parse_dhcpv6_options_with, DHCPv6Option,
+};
+use crate::structs::status_code::StatusCode;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::net::Ipv6Addr;
 use nom::number::complete::{be_u24, be_u8};
 use nom::sequence::tuple;
-use nom::IResult;
-use std::net::Ipv6Addr;
 
-use crate::utils::parse_ipv6_address;
+use crate::utils::{encode_ipv6_address, parse_ipv6_address};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum DHCPv6Header<'a> {
@@ -21,13 +35,159 @@ pub enum DHCPv6Header<'a> {
         peer_address: Ipv6Addr,
         options: Vec<DHCPv6Option<'a>>,
     },
+    DHCPv4Over6 {
+        message_type: DHCPv6MessageType,
+        flags: u32,
+        options: Vec<DHCPv6Option<'a>>,
+    },
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct IaStatus<'h> {
+    pub option: OptionCode,
+    pub id: u32,
+    pub status: StatusCode,
+    pub message: Cow<'h, str>,
+}
+
+impl<'a> DHCPv6Header<'a> {
+    pub fn options(&self) -> &[DHCPv6Option<'a>] {
+        match self {
+            DHCPv6Header::ClientServer { options, .. }
+            | DHCPv6Header::RelayAgentServer { options, .. }
+            | DHCPv6Header::DHCPv4Over6 { options, .. } => options,
+        }
+    }
+
+    pub fn status(&self) -> Option<(StatusCode, Cow<'_, str>)> {
+        self.options().iter().find_map(|option| match option {
+            DHCPv6Option::StatusCode { code, message } => {
+                Some((*code, String::from_utf8_lossy(message)))
+            }
+            _ => None,
+        })
+    }
+
+    pub fn interface_id(&self) -> Option<&[u8]> {
+        find_interface_id(self.options())
+    }
+
+    pub fn remote_id(&self) -> Option<(u32, &[u8])> {
+        find_remote_id(self.options())
+    }
+
+    pub fn subscriber_id(&self) -> Option<&[u8]> {
+        find_subscriber_id(self.options())
+    }
+
+    pub fn client_link_layer_address(&self) -> Option<(HardwareType, &[u8])> {
+        find_client_link_layer_address(self.options())
+    }
+
+    pub fn client_mac_address(&self) -> Option<MacAddr> {
+        let (hardware_type, address) = self.client_link_layer_address()?;
+
+        MacAddr::from_link_layer(hardware_type, address)
+    }
+
+    // An IA without a Status Code option is successful, see RFC 8415
+    // section 21.13.
+    pub fn ia_statuses(&self) -> impl Iterator<Item = IaStatus<'_>> {
+        self.options().iter().filter_map(|option| {
+            let id = option.ia_id()?;
+            let (status, message) = option
+                .status()
+                .unwrap_or((StatusCode::Success, Cow::Borrowed("")));
+
+            Some(IaStatus {
+                option: option.code(),
+                id,
+                status,
+                message,
+            })
+        })
+    }
+
+    pub fn into_owned(self) -> DHCPv6Header<'static> {
+        match self {
+            DHCPv6Header::ClientServer {
+                message_type,
+                transaction_id,
+                options,
+            } => DHCPv6Header::ClientServer {
+                message_type,
+                transaction_id,
+                options: options_into_owned(options),
+            },
+            DHCPv6Header::RelayAgentServer {
+                message_type,
+                hop_count,
+                link_address,
+                peer_address,
+                options,
+            } => DHCPv6Header::RelayAgentServer {
+                message_type,
+                hop_count,
+                link_address,
+                peer_address,
+                options: options_into_owned(options),
+            },
+            DHCPv6Header::DHCPv4Over6 {
+                message_type,
+                flags,
+                options,
+            } => DHCPv6Header::DHCPv4Over6 {
+                message_type,
+                flags,
+                options: options_into_owned(options),
+            },
+        }
+    }
+
+    pub fn as_borrowed(&self) -> DHCPv6Header<'_> {
+        match self {
+            DHCPv6Header::ClientServer {
+                message_type,
+                transaction_id,
+                options,
+            } => DHCPv6Header::ClientServer {
+                message_type: *message_type,
+                transaction_id: *transaction_id,
+                options: options_as_borrowed(options),
+            },
+            DHCPv6Header::RelayAgentServer {
+                message_type,
+                hop_count,
+                link_address,
+                peer_address,
+                options,
+            } => DHCPv6Header::RelayAgentServer {
+                message_type: *message_type,
+                hop_count: *hop_count,
+                link_address: *link_address,
+                peer_address: *peer_address,
+                options: options_as_borrowed(options),
+            },
+            DHCPv6Header::DHCPv4Over6 {
+                message_type,
+                flags,
+                options,
+            } => DHCPv6Header::DHCPv4Over6 {
+                message_type: *message_type,
+                flags: *flags,
+                options: options_as_borrowed(options),
+            },
+        }
+    }
 }
 
-fn parse_dhcpv6_header_client_server(
-    input: &[u8],
+fn parse_dhcpv6_header_client_server<'a>(
+    config: &ParseConfig,
+    input: &'a [u8],
     message_type: DHCPv6MessageType,
-) -> IResult<&[u8], DHCPv6Header> {
-    let (rest, (transaction_id, options)) = tuple((be_u24, parse_dhcpv6_options))(input)?;
+) -> ParseResult<'a, DHCPv6Header<'a>> {
+    let (rest, transaction_id) = with_kind(ErrorKind::TruncatedHeader, be_u24)(input)?;
+    let (rest, options) = parse_dhcpv6_options_with(config, rest)?;
 
     Ok((
         rest,
@@ -39,16 +199,34 @@ fn parse_dhcpv6_header_client_server(
     ))
 }
 
-fn parse_dhcpv6_header_relay_agent_server(
-    input: &[u8],
+fn parse_dhcpv6_header_dhcpv4_over_6<'a>(
+    config: &ParseConfig,
+    input: &'a [u8],
     message_type: DHCPv6MessageType,
-) -> IResult<&[u8], DHCPv6Header> {
-    let (rest, (hop_count, link_address, peer_address, options)) = tuple((
-        be_u8,
-        parse_ipv6_address,
-        parse_ipv6_address,
-        parse_dhcpv6_options,
-    ))(input)?;
+) -> ParseResult<'a, DHCPv6Header<'a>> {
+    let (rest, flags) = with_kind(ErrorKind::TruncatedHeader, be_u24)(input)?;
+    let (rest, options) = parse_dhcpv6_options_with(config, rest)?;
+
+    Ok((
+        rest,
+        DHCPv6Header::DHCPv4Over6 {
+            message_type,
+            flags,
+            options,
+        },
+    ))
+}
+
+fn parse_dhcpv6_header_relay_agent_server<'a>(
+    config: &ParseConfig,
+    input: &'a [u8],
+    message_type: DHCPv6MessageType,
+) -> ParseResult<'a, DHCPv6Header<'a>> {
+    let (rest, (hop_count, link_address, peer_address)) = with_kind(
+        ErrorKind::TruncatedHeader,
+        tuple((be_u8, parse_ipv6_address, parse_ipv6_address)),
+    )(input)?;
+    let (rest, options) = parse_dhcpv6_options_with(config, rest)?;
 
     Ok((
         rest,
@@ -62,19 +240,154 @@ fn parse_dhcpv6_header_relay_agent_server(
     ))
 }
 
-pub fn parse_dhcpv6_header(input: &[u8]) -> IResult<&[u8], DHCPv6Header> {
+pub fn parse_dhcpv6_header(input: &[u8]) -> ParseResult<'_, DHCPv6Header<'_>> {
+    parse_dhcpv6_header_with(&ParseConfig::default(), input)
+}
+
+pub fn parse_dhcpv6_header_with<'a>(
+    config: &ParseConfig,
+    input: &'a [u8],
+) -> ParseResult<'a, DHCPv6Header<'a>> {
     let (rest, message_type) = parse_dhcpv6_message_type(input)?;
     match message_type {
         DHCPv6MessageType::RelayForw | DHCPv6MessageType::RelayRepl => {
-            parse_dhcpv6_header_relay_agent_server(rest, message_type)
+            parse_dhcpv6_header_relay_agent_server(config, rest, message_type)
+        }
+        DHCPv6MessageType::DHCPv4Query | DHCPv6MessageType::DHCPv4Response => {
+            parse_dhcpv6_header_dhcpv4_over_6(config, rest, message_type)
+        }
+        _ => parse_dhcpv6_header_client_server(config, rest, message_type),
+    }
+}
+
+pub fn parse_message(input: &[u8]) -> Result<DHCPv6Header<'_>, Error> {
+    parse_message_with(&ParseConfig::default(), input)
+}
+
+pub fn parse_message_with<'a>(
+    config: &ParseConfig,
+    input: &'a [u8],
+) -> Result<DHCPv6Header<'a>, Error> {
+    let (_, header) =
+        parse_dhcpv6_header_with(config, input).map_err(|e| Error::from_nom_error(input, e))?;
+
+    Ok(header)
+}
+
+pub fn dhcpv6_header_len(header: &DHCPv6Header) -> usize {
+    match header {
+        DHCPv6Header::ClientServer { options, .. } | DHCPv6Header::DHCPv4Over6 { options, .. } => {
+            4 + dhcpv6_options_len(options)
         }
-        _ => parse_dhcpv6_header_client_server(rest, message_type),
+        DHCPv6Header::RelayAgentServer { options, .. } => 34 + dhcpv6_options_len(options),
     }
 }
 
+// The header layout follows from the message type, so a header is only
+// encoded when parsing it back yields the same variant.
+fn check_message_type(header: &DHCPv6Header) -> Result<(), EncodeError> {
+    let is_relay = |message_type: DHCPv6MessageType| {
+        message_type == DHCPv6MessageType::RelayForw || message_type == DHCPv6MessageType::RelayRepl
+    };
+    let is_dhcpv4_over_6 = |message_type: DHCPv6MessageType| {
+        message_type == DHCPv6MessageType::DHCPv4Query
+            || message_type == DHCPv6MessageType::DHCPv4Response
+    };
+
+    let (message_type, fits) = match header {
+        DHCPv6Header::ClientServer { message_type, .. } => (
+            message_type,
+            message_type.to_u8() != 0
+                && !is_relay(*message_type)
+                && !is_dhcpv4_over_6(*message_type),
+        ),
+        DHCPv6Header::RelayAgentServer { message_type, .. } => {
+            (message_type, is_relay(*message_type))
+        }
+        DHCPv6Header::DHCPv4Over6 { message_type, .. } => {
+            (message_type, is_dhcpv4_over_6(*message_type))
+        }
+    };
+
+    if fits {
+        Ok(())
+    } else {
+        Err(EncodeError::UnexpectedMessageType(message_type.to_u8()))
+    }
+}
+
+pub fn encode_dhcpv6_header<S: Sink>(
+    header: &DHCPv6Header,
+    output: &mut S,
+) -> Result<(), EncodeError> {
+    check_message_type(header)?;
+
+    match header {
+        DHCPv6Header::ClientServer {
+            message_type,
+            transaction_id,
+            options,
+        } => {
+            if *transaction_id > 0x00ff_ffff {
+                return Err(EncodeError::TransactionIdOutOfRange(*transaction_id));
+            }
+            encode_dhcpv6_message_type(message_type, output);
+            output.put(&transaction_id.to_be_bytes()[1..]);
+            encode_dhcpv6_options(options, output)
+        }
+        DHCPv6Header::DHCPv4Over6 {
+            message_type,
+            flags,
+            options,
+        } => {
+            if *flags > 0x00ff_ffff {
+                return Err(EncodeError::FlagsOutOfRange(*flags));
+            }
+            encode_dhcpv6_message_type(message_type, output);
+            output.put(&flags.to_be_bytes()[1..]);
+            encode_dhcpv6_options(options, output)
+        }
+        DHCPv6Header::RelayAgentServer {
+            message_type,
+            hop_count,
+            link_address,
+            peer_address,
+            options,
+        } => {
+            encode_dhcpv6_message_type(message_type, output);
+            output.put(&[*hop_count]);
+            encode_ipv6_address(link_address, output);
+            encode_ipv6_address(peer_address, output);
+            encode_dhcpv6_options(options, output)
+        }
+    }
+}
+
+pub fn encode_dhcpv6_header_into(
+    header: &DHCPv6Header,
+    buffer: &mut [u8],
+) -> Result<usize, EncodeError> {
+    let needed = dhcpv6_header_len(header);
+    if buffer.len() < needed {
+        return Err(EncodeError::BufferTooSmall {
+            needed,
+            available: buffer.len(),
+        });
+    }
+
+    let mut output = SliceSink::new(buffer);
+    encode_dhcpv6_header(header, &mut output)?;
+
+    Ok(output.position())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::structs::duid::Duid;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use std::borrow::Cow;
 
     #[test]
     fn test_valid_dhcpv6_header() {
@@ -86,9 +399,329 @@ mod tests {
                 DHCPv6Header::ClientServer {
                     message_type: DHCPv6MessageType::Solicit,
                     transaction_id: 1,
-                    options: vec![DHCPv6Option::CliendID { duid: &b"toto"[..] }]
+                    options: vec![DHCPv6Option::CliendID {
+                        duid: Duid::Other {
+                            duid_type: 0x746f,
+                            data: Cow::Borrowed(&b"to"[..])
+                        }
+                    }]
                 }
             ))
         );
     }
+
+    #[test]
+    fn test_valid_dhcpv4_over_6_header() {
+        let input = b"\x14\x80\x00\x00\x00\x57\x00\x04toto";
+        assert_eq!(
+            parse_dhcpv6_header(&input[..]),
+            Ok((
+                &b""[..],
+                DHCPv6Header::DHCPv4Over6 {
+                    message_type: DHCPv6MessageType::DHCPv4Query,
+                    flags: 0x800000,
+                    options: vec![DHCPv6Option::Unknown {
+                        code: 87,
+                        data: Cow::Borrowed(&b"toto"[..])
+                    }]
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_roundtrip_dhcpv6_header() {
+        let inputs: &[&[u8]] = &[
+            b"\x01\x00\x00\x01\x00\x01\x00\x04toto",
+            b"\x0c\x01\
+              \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\
+              \xfe\x80\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x01\
+              \x00\x12\x00\x04eth0\
+              \x00\x09\x00\x0c\x01\x00\x00\x01\x00\x01\x00\x04toto",
+            b"\x0e\x00\x00\x01\x00\x01\x00\x04toto",
+            b"\x15\x00\x00\x00\x00\x57\x00\x04toto",
+            b"\xfe\x00\x00\x01",
+        ];
+
+        for input in inputs {
+            let (_, header) = parse_dhcpv6_header(input).unwrap();
+            assert_eq!(dhcpv6_header_len(&header), input.len());
+
+            let mut output = Vec::new();
+            encode_dhcpv6_header(&header, &mut output).unwrap();
+            assert_eq!(&output[..], *input);
+
+            let mut buffer = [0u8; 128];
+            let len = encode_dhcpv6_header_into(&header, &mut buffer[..]).unwrap();
+            assert_eq!(&buffer[..len], *input);
+            assert_eq!(parse_dhcpv6_header(&buffer[..len]), Ok((&b""[..], header)));
+        }
+    }
+
+    #[test]
+    fn test_ia_statuses() {
+        let input = b"\x07\x00\x00\x01\
+              \x00\x03\x00\x0c\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\
+              \x00\x19\x00\x20\x00\x00\x00\x02\x00\x00\x00\x00\x00\x00\x00\x00\
+              \x00\x0d\x00\x10\x00\x06no prefix left\
+              \x00\x0d\x00\x04\x00\x00ok";
+        let (_, header) = parse_dhcpv6_header(&input[..]).unwrap();
+
+        assert_eq!(header.status(), Some((StatusCode::Success, "ok".into())));
+        assert_eq!(
+            header.ia_statuses().collect::<Vec<_>>(),
+            vec![
+                IaStatus {
+                    option: OptionCode::IaNa,
+                    id: 1,
+                    status: StatusCode::Success,
+                    message: "".into(),
+                },
+                IaStatus {
+                    option: OptionCode::IaPd,
+                    id: 2,
+                    status: StatusCode::NoPrefixAvail,
+                    message: "no prefix left".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_owned_dhcpv6_header() {
+        let input = b"\x07\x00\x00\x01\
+              \x00\x02\x00\x0a\x00\x03\x00\x01\x00\x11\x22\xaa\xbb\xcc\
+              \x00\x03\x00\x12\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\
+              \x00\x0d\x00\x02\x00\x00\
+              \x00\x0f\x00\x04toto"
+            .to_vec();
+        let (_, header) = parse_dhcpv6_header(&input).unwrap();
+        let expected: DHCPv6Header<'static> = DHCPv6Header::ClientServer {
+            message_type: DHCPv6MessageType::Reply,
+            transaction_id: 1,
+            options: vec![
+                DHCPv6Option::ServerID {
+                    duid: Duid::LinkLayer {
+                        hardware_type: 1,
+                        link_layer_address: Cow::Owned(b"\x00\x11\x22\xaa\xbb\xcc".to_vec()),
+                    },
+                },
+                DHCPv6Option::IdentityAssociationForNonTemporaryAddresses {
+                    id: 1,
+                    time_1: 0,
+                    time_2: 0,
+                    options: vec![DHCPv6Option::StatusCode {
+                        code: StatusCode::Success,
+                        message: Cow::Owned(Vec::new()),
+                    }],
+                },
+                DHCPv6Option::UserClass {
+                    data: Cow::Owned(b"toto".to_vec()),
+                },
+            ],
+        };
+
+        let owned: DHCPv6Header<'static> = header.into_owned();
+        drop(input);
+
+        assert_eq!(owned, expected);
+        assert_eq!(owned.as_borrowed(), owned);
+
+        let mut output = Vec::new();
+        encode_dhcpv6_header(&owned.as_borrowed(), &mut output).unwrap();
+        assert_eq!(parse_dhcpv6_header(&output), Ok((&b""[..], owned)));
+    }
+
+    #[test]
+    fn test_encode_dhcpv6_header_errors() {
+        let header = DHCPv6Header::DHCPv4Over6 {
+            message_type: DHCPv6MessageType::DHCPv4Response,
+            flags: 0x0100_0000,
+            options: vec![],
+        };
+        assert_eq!(
+            encode_dhcpv6_header(&header, &mut Vec::new()),
+            Err(EncodeError::FlagsOutOfRange(0x0100_0000))
+        );
+
+        let header = DHCPv6Header::ClientServer {
+            message_type: DHCPv6MessageType::Solicit,
+            transaction_id: 0x0100_0000,
+            options: vec![],
+        };
+        assert_eq!(
+            encode_dhcpv6_header(&header, &mut Vec::new()),
+            Err(EncodeError::TransactionIdOutOfRange(0x0100_0000))
+        );
+
+        let header = DHCPv6Header::ClientServer {
+            message_type: DHCPv6MessageType::RelayForw,
+            transaction_id: 1,
+            options: vec![],
+        };
+        assert_eq!(
+            encode_dhcpv6_header(&header, &mut Vec::new()),
+            Err(EncodeError::UnexpectedMessageType(12))
+        );
+
+        let header = DHCPv6Header::ClientServer {
+            message_type: DHCPv6MessageType::DHCPv4Query,
+            transaction_id: 1,
+            options: vec![],
+        };
+        assert_eq!(
+            encode_dhcpv6_header(&header, &mut Vec::new()),
+            Err(EncodeError::UnexpectedMessageType(20))
+        );
+
+        let header = DHCPv6Header::ClientServer {
+            message_type: DHCPv6MessageType::Unassigned(0),
+            transaction_id: 1,
+            options: vec![],
+        };
+        assert_eq!(
+            encode_dhcpv6_header(&header, &mut Vec::new()),
+            Err(EncodeError::UnexpectedMessageType(0))
+        );
+
+        let header = DHCPv6Header::RelayAgentServer {
+            message_type: DHCPv6MessageType::Solicit,
+            hop_count: 0,
+            link_address: Ipv6Addr::UNSPECIFIED,
+            peer_address: Ipv6Addr::UNSPECIFIED,
+            options: vec![],
+        };
+        assert_eq!(
+            encode_dhcpv6_header(&header, &mut Vec::new()),
+            Err(EncodeError::UnexpectedMessageType(1))
+        );
+
+        let header = DHCPv6Header::DHCPv4Over6 {
+            message_type: DHCPv6MessageType::Unassigned(13),
+            flags: 0,
+            options: vec![],
+        };
+        assert_eq!(
+            encode_dhcpv6_header(&header, &mut Vec::new()),
+            Err(EncodeError::UnexpectedMessageType(13))
+        );
+
+        let header = DHCPv6Header::ClientServer {
+            message_type: DHCPv6MessageType::Solicit,
+            transaction_id: 1,
+            options: vec![DHCPv6Option::CliendID {
+                duid: Duid::Other {
+                    duid_type: 0x746f,
+                    data: Cow::Borrowed(&b"to"[..]),
+                },
+            }],
+        };
+        assert_eq!(
+            encode_dhcpv6_header_into(&header, &mut [0u8; 8][..]),
+            Err(EncodeError::BufferTooSmall {
+                needed: 12,
+                available: 8
+            })
+        );
+    }
+
+    #[test]
+    fn test_trailing_garbage_dhcpv6_header() {
+        let input = b"\x01\x00\x00\x01\x00\x01\x00\x04toto\x00\x01";
+        assert!(parse_dhcpv6_header(&input[..]).is_err());
+    }
+
+    #[test]
+    fn test_parse_message_errors() {
+        let input = b"\x01\x00\x00";
+        let error = parse_message(&input[..]).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::TruncatedHeader);
+
+        let input = b"\x00\x00\x00\x01";
+        let error = parse_message(&input[..]).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::UnknownMessageType(0));
+        assert_eq!(error.offset, 0);
+
+        let input = b"\x01\x00\x00\x01\x00\x01\x00\x04toto\x00\x08\x00\x03\x00\x00\x00";
+        let error = parse_message(&input[..]).unwrap_err();
+        assert_eq!(
+            error.kind,
+            ErrorKind::BadOptionLength { code: 8, length: 3 }
+        );
+        assert_eq!(error.offset, 12);
+        assert_eq!(error.path, vec![8]);
+
+        let input = b"\x01\x00\x00\x01\x00\x0d\x00\x03\x00\x00\xff";
+        let error = parse_message(&input[..]).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::InvalidUtf8);
+        assert_eq!(error.offset, 10);
+        assert_eq!(error.path, vec![13]);
+
+        let input = b"\x01\x00\x00\x01\x00\x01\x00\x04to";
+        let error = parse_message(&input[..]).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::TruncatedOption);
+        assert_eq!(error.offset, 4);
+        assert_eq!(error.path, vec![1]);
+
+        let input = b"\x01\x00\x00\x01\x00\x01\x00";
+        let error = parse_message(&input[..]).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::TrailingBytes);
+        assert_eq!(error.offset, 4);
+    }
+
+    #[test]
+    fn test_parse_message_with() {
+        let input = b"\x01\x00\x00\x01\
+              \x00\x08\x00\x03\x00\x00\x00\
+              \x00\x0d\x00\x03\x00\x00\xff\
+              \x00\x0e";
+        let header = parse_message_with(&ParseConfig::tolerant(), &input[..]).unwrap();
+        assert_eq!(
+            header.options(),
+            &[
+                DHCPv6Option::Malformed {
+                    code: 8,
+                    data: Cow::Borrowed(&b"\x00\x00\x00"[..])
+                },
+                DHCPv6Option::StatusCode {
+                    code: StatusCode::Success,
+                    message: Cow::Borrowed(&b"\xff"[..])
+                },
+            ]
+        );
+
+        let mut output = Vec::new();
+        encode_dhcpv6_header(&header, &mut output).unwrap();
+        assert_eq!(output, &input[..input.len() - 2]);
+
+        let config = ParseConfig {
+            max_options: 1,
+            ..ParseConfig::tolerant()
+        };
+        let error = parse_message_with(&config, &input[..]).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::TooManyOptions);
+        assert_eq!(error.offset, 11);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_dhcpv6_header_never_panics(input in vec(any::<u8>(), 0..1024)) {
+            let _ = parse_dhcpv6_header(&input[..]);
+        }
+
+        #[test]
+        fn test_parse_dhcpv6_header_roundtrip(
+            message_type in 1u8..=255,
+            body in vec(any::<u8>(), 0..256),
+        ) {
+            let mut input = vec![message_type];
+            input.extend_from_slice(&body);
+            if let Ok((rest, header)) = parse_dhcpv6_header(&input[..]) {
+                prop_assert!(rest.is_empty());
+                let mut output = Vec::new();
+                encode_dhcpv6_header(&header, &mut output).unwrap();
+                prop_assert_eq!(output, input);
+            }
+        }
+    }
 }
@@ -0,0 +1,108 @@
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct CircuitId<'a> {
+    pub access_node: &'a str,
+    pub technology: &'a str,
+    pub location: &'a str,
+}
+
+impl fmt::Display for CircuitId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.access_node, self.technology, self.location
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum InterfaceId<'a> {
+    Name(&'a str),
+    CircuitId(CircuitId<'a>),
+    Binary(&'a [u8]),
+}
+
+impl<'a> InterfaceId<'a> {
+    pub fn decode(data: &'a [u8]) -> Self {
+        let text = match core::str::from_utf8(data) {
+            Ok(text) if !text.is_empty() && text.bytes().all(|b| (0x20..=0x7e).contains(&b)) => {
+                text
+            }
+            _ => return InterfaceId::Binary(data),
+        };
+
+        match parse_circuit_id(text) {
+            Some(circuit_id) => InterfaceId::CircuitId(circuit_id),
+            None => InterfaceId::Name(text),
+        }
+    }
+}
+
+// Broadband Forum TR-101 section 3.9.3 default format:
+// "Access-Node-Identifier atm slot/port:vpi.vci" or
+// "Access-Node-Identifier eth slot/port[:vlan-id]".
+fn parse_circuit_id(text: &str) -> Option<CircuitId<'_>> {
+    let mut parts = text.rsplitn(3, ' ');
+    let location = parts.next()?;
+    let technology = parts.next()?;
+    let access_node = parts.next()?;
+
+    if access_node.is_empty() || !matches!(technology, "atm" | "eth") || !location.contains('/') {
+        return None;
+    }
+
+    Some(CircuitId {
+        access_node,
+        technology,
+        location,
+    })
+}
+
+impl fmt::Display for InterfaceId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InterfaceId::Name(name) => f.write_str(name),
+            InterfaceId::CircuitId(circuit_id) => circuit_id.fmt(f),
+            InterfaceId::Binary(data) => {
+                for byte in data.iter() {
+                    write!(f, "{:02x}", byte)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_interface_id() {
+        assert_eq!(InterfaceId::decode(b"eth0"), InterfaceId::Name("eth0"));
+        assert_eq!(
+            InterfaceId::decode(b"dslam-7 atm 1/3:8.35"),
+            InterfaceId::CircuitId(CircuitId {
+                access_node: "dslam-7",
+                technology: "atm",
+                location: "1/3:8.35",
+            })
+        );
+        assert_eq!(
+            InterfaceId::decode(b"olt 2 north eth 0/12:100").to_string(),
+            "olt 2 north eth 0/12:100"
+        );
+        assert_eq!(
+            InterfaceId::decode(b"uplink eth ge0"),
+            InterfaceId::Name("uplink eth ge0")
+        );
+        assert_eq!(
+            InterfaceId::decode(b"\x00\x00\x00\x05"),
+            InterfaceId::Binary(b"\x00\x00\x00\x05")
+        );
+        assert_eq!(InterfaceId::decode(b"\x00\x05").to_string(), "0005");
+        assert_eq!(InterfaceId::decode(b""), InterfaceId::Binary(b""));
+    }
+}
@@ -0,0 +1,144 @@
+use core::array::TryFromSliceError;
+use core::convert::TryFrom;
+use core::fmt;
+
+// IANA "Hardware Types" registry, see RFC 826 and RFC 5494.
+#[derive(Debug, Clone, Copy)]
+pub enum HardwareType {
+    Ethernet,
+    ExperimentalEthernet,
+    Ax25,
+    ProNetTokenRing,
+    Chaos,
+    Ieee802,
+    Arcnet,
+    FrameRelay,
+    Atm,
+    Hdlc,
+    FibreChannel,
+    SerialLine,
+    Ieee1394,
+    Eui64,
+    Infiniband,
+    PureIp,
+    Other(u16),
+}
+
+impl HardwareType {
+    pub fn from_u16(value: u16) -> Self {
+        match value {
+            1 => HardwareType::Ethernet,
+            2 => HardwareType::ExperimentalEthernet,
+            3 => HardwareType::Ax25,
+            4 => HardwareType::ProNetTokenRing,
+            5 => HardwareType::Chaos,
+            6 => HardwareType::Ieee802,
+            7 => HardwareType::Arcnet,
+            15 => HardwareType::FrameRelay,
+            16 => HardwareType::Atm,
+            17 => HardwareType::Hdlc,
+            18 => HardwareType::FibreChannel,
+            20 => HardwareType::SerialLine,
+            24 => HardwareType::Ieee1394,
+            27 => HardwareType::Eui64,
+            32 => HardwareType::Infiniband,
+            35 => HardwareType::PureIp,
+            _ => HardwareType::Other(value),
+        }
+    }
+
+    pub fn to_u16(self) -> u16 {
+        match self {
+            HardwareType::Ethernet => 1,
+            HardwareType::ExperimentalEthernet => 2,
+            HardwareType::Ax25 => 3,
+            HardwareType::ProNetTokenRing => 4,
+            HardwareType::Chaos => 5,
+            HardwareType::Ieee802 => 6,
+            HardwareType::Arcnet => 7,
+            HardwareType::FrameRelay => 15,
+            HardwareType::Atm => 16,
+            HardwareType::Hdlc => 17,
+            HardwareType::FibreChannel => 18,
+            HardwareType::SerialLine => 20,
+            HardwareType::Ieee1394 => 24,
+            HardwareType::Eui64 => 27,
+            HardwareType::Infiniband => 32,
+            HardwareType::PureIp => 35,
+            HardwareType::Other(value) => value,
+        }
+    }
+}
+
+impl_eq_by_value!(HardwareType, to_u16);
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct MacAddr(pub [u8; 6]);
+
+impl MacAddr {
+    pub fn octets(&self) -> [u8; 6] {
+        self.0
+    }
+
+    pub fn from_link_layer(hardware_type: HardwareType, address: &[u8]) -> Option<Self> {
+        if hardware_type == HardwareType::Ethernet {
+            MacAddr::try_from(address).ok()
+        } else {
+            None
+        }
+    }
+}
+
+impl TryFrom<&[u8]> for MacAddr {
+    type Error = TryFromSliceError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        <[u8; 6]>::try_from(value).map(MacAddr)
+    }
+}
+
+impl fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(
+            f,
+            "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+            a, b, c, d, e, g
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mac_addr() {
+        let mac = MacAddr::try_from(&b"\x00\x11\x22\xaa\xbb\xcc"[..]).unwrap();
+        assert_eq!(mac.to_string(), "00:11:22:aa:bb:cc");
+        assert!(MacAddr::try_from(&b"\x00\x11"[..]).is_err());
+        assert_eq!(
+            MacAddr::from_link_layer(HardwareType::Ethernet, b"\x00\x11\x22\xaa\xbb\xcc"),
+            Some(mac)
+        );
+        assert_eq!(
+            MacAddr::from_link_layer(HardwareType::Ieee1394, b"\x00\x11\x22\xaa\xbb\xcc"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_hardware_type() {
+        for value in 0..=u16::MAX {
+            assert_eq!(HardwareType::from_u16(value).to_u16(), value);
+        }
+        assert_eq!(HardwareType::from_u16(1), HardwareType::Ethernet);
+        assert_eq!(HardwareType::from_u16(32), HardwareType::Infiniband);
+        assert_eq!(HardwareType::from_u16(19), HardwareType::Other(19));
+        assert_eq!(HardwareType::Other(1), HardwareType::Ethernet);
+        assert_eq!(
+            MacAddr::from_link_layer(HardwareType::Other(1), b"\x00\x11\x22\xaa\xbb\xcc"),
+            Some(MacAddr([0x00, 0x11, 0x22, 0xaa, 0xbb, 0xcc]))
+        );
+    }
+}
@@ -1,33 +1,158 @@
-use nom::combinator::map_opt;
 use nom::number::complete::be_u8;
-use nom::IResult;
-use num_traits::FromPrimitive;
 
-#[derive(Debug, Clone, Eq, PartialEq, Primitive)]
-#[repr(u8)]
+use crate::encode::Sink;
+use crate::error::{fail, with_kind, ErrorKind, ParseResult};
+
+#[derive(Debug, Clone, Copy)]
 pub enum DHCPv6MessageType {
-    Solicit = 1,
-    Advertise = 2,
-    Request = 3,
-    Confirm = 4,
-    Renew = 5,
-    Rebind = 6,
-    Reply = 7,
-    Release = 8,
-    Decline = 9,
-    Reconfigure = 10,
-    InformationRequest = 11,
-    RelayForw = 12,
-    RelayRepl = 13,
+    Solicit,
+    Advertise,
+    Request,
+    Confirm,
+    Renew,
+    Rebind,
+    Reply,
+    Release,
+    Decline,
+    Reconfigure,
+    InformationRequest,
+    RelayForw,
+    RelayRepl,
+    Leasequery,
+    LeasequeryReply,
+    LeasequeryDone,
+    LeasequeryData,
+    ReconfigureRequest,
+    ReconfigureReply,
+    DHCPv4Query,
+    DHCPv4Response,
+    ActiveLeasequery,
+    StartTLS,
+    BndUpd,
+    BndReply,
+    PoolReq,
+    PoolResp,
+    UpdReq,
+    UpdReqAll,
+    UpdDone,
+    Connect,
+    ConnectReply,
+    Disconnect,
+    State,
+    Contact,
+    AddrRegInform,
+    AddrRegReply,
+    Unassigned(u8),
+}
+
+impl DHCPv6MessageType {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => None,
+            1 => Some(DHCPv6MessageType::Solicit),
+            2 => Some(DHCPv6MessageType::Advertise),
+            3 => Some(DHCPv6MessageType::Request),
+            4 => Some(DHCPv6MessageType::Confirm),
+            5 => Some(DHCPv6MessageType::Renew),
+            6 => Some(DHCPv6MessageType::Rebind),
+            7 => Some(DHCPv6MessageType::Reply),
+            8 => Some(DHCPv6MessageType::Release),
+            9 => Some(DHCPv6MessageType::Decline),
+            10 => Some(DHCPv6MessageType::Reconfigure),
+            11 => Some(DHCPv6MessageType::InformationRequest),
+            12 => Some(DHCPv6MessageType::RelayForw),
+            13 => Some(DHCPv6MessageType::RelayRepl),
+            14 => Some(DHCPv6MessageType::Leasequery),
+            15 => Some(DHCPv6MessageType::LeasequeryReply),
+            16 => Some(DHCPv6MessageType::LeasequeryDone),
+            17 => Some(DHCPv6MessageType::LeasequeryData),
+            18 => Some(DHCPv6MessageType::ReconfigureRequest),
+            19 => Some(DHCPv6MessageType::ReconfigureReply),
+            20 => Some(DHCPv6MessageType::DHCPv4Query),
+            21 => Some(DHCPv6MessageType::DHCPv4Response),
+            22 => Some(DHCPv6MessageType::ActiveLeasequery),
+            23 => Some(DHCPv6MessageType::StartTLS),
+            24 => Some(DHCPv6MessageType::BndUpd),
+            25 => Some(DHCPv6MessageType::BndReply),
+            26 => Some(DHCPv6MessageType::PoolReq),
+            27 => Some(DHCPv6MessageType::PoolResp),
+            28 => Some(DHCPv6MessageType::UpdReq),
+            29 => Some(DHCPv6MessageType::UpdReqAll),
+            30 => Some(DHCPv6MessageType::UpdDone),
+            31 => Some(DHCPv6MessageType::Connect),
+            32 => Some(DHCPv6MessageType::ConnectReply),
+            33 => Some(DHCPv6MessageType::Disconnect),
+            34 => Some(DHCPv6MessageType::State),
+            35 => Some(DHCPv6MessageType::Contact),
+            36 => Some(DHCPv6MessageType::AddrRegInform),
+            37 => Some(DHCPv6MessageType::AddrRegReply),
+            _ => Some(DHCPv6MessageType::Unassigned(value)),
+        }
+    }
+
+    pub fn to_u8(self) -> u8 {
+        match self {
+            DHCPv6MessageType::Solicit => 1,
+            DHCPv6MessageType::Advertise => 2,
+            DHCPv6MessageType::Request => 3,
+            DHCPv6MessageType::Confirm => 4,
+            DHCPv6MessageType::Renew => 5,
+            DHCPv6MessageType::Rebind => 6,
+            DHCPv6MessageType::Reply => 7,
+            DHCPv6MessageType::Release => 8,
+            DHCPv6MessageType::Decline => 9,
+            DHCPv6MessageType::Reconfigure => 10,
+            DHCPv6MessageType::InformationRequest => 11,
+            DHCPv6MessageType::RelayForw => 12,
+            DHCPv6MessageType::RelayRepl => 13,
+            DHCPv6MessageType::Leasequery => 14,
+            DHCPv6MessageType::LeasequeryReply => 15,
+            DHCPv6MessageType::LeasequeryDone => 16,
+            DHCPv6MessageType::LeasequeryData => 17,
+            DHCPv6MessageType::ReconfigureRequest => 18,
+            DHCPv6MessageType::ReconfigureReply => 19,
+            DHCPv6MessageType::DHCPv4Query => 20,
+            DHCPv6MessageType::DHCPv4Response => 21,
+            DHCPv6MessageType::ActiveLeasequery => 22,
+            DHCPv6MessageType::StartTLS => 23,
+            DHCPv6MessageType::BndUpd => 24,
+            DHCPv6MessageType::BndReply => 25,
+            DHCPv6MessageType::PoolReq => 26,
+            DHCPv6MessageType::PoolResp => 27,
+            DHCPv6MessageType::UpdReq => 28,
+            DHCPv6MessageType::UpdReqAll => 29,
+            DHCPv6MessageType::UpdDone => 30,
+            DHCPv6MessageType::Connect => 31,
+            DHCPv6MessageType::ConnectReply => 32,
+            DHCPv6MessageType::Disconnect => 33,
+            DHCPv6MessageType::State => 34,
+            DHCPv6MessageType::Contact => 35,
+            DHCPv6MessageType::AddrRegInform => 36,
+            DHCPv6MessageType::AddrRegReply => 37,
+            DHCPv6MessageType::Unassigned(value) => value,
+        }
+    }
 }
 
-pub fn parse_dhcpv6_message_type(input: &[u8]) -> IResult<&[u8], DHCPv6MessageType> {
-    map_opt(be_u8, DHCPv6MessageType::from_u8)(input)
+impl_eq_by_value!(DHCPv6MessageType, to_u8);
+
+pub fn parse_dhcpv6_message_type(input: &[u8]) -> ParseResult<'_, DHCPv6MessageType> {
+    let (rest, value) = with_kind(ErrorKind::TruncatedHeader, be_u8)(input)?;
+
+    match DHCPv6MessageType::from_u8(value) {
+        Some(message_type) => Ok((rest, message_type)),
+        None => fail(input, ErrorKind::UnknownMessageType(value)),
+    }
 }
 
-#[cfg(test)]
+pub fn encode_dhcpv6_message_type<S: Sink>(message_type: &DHCPv6MessageType, output: &mut S) {
+    output.put(&[message_type.to_u8()]);
+}
+
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
+    use crate::error::ParseError;
 
     #[test]
     fn test_valid_dhcpv6_message_type() {
@@ -85,9 +210,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_encode_dhcpv6_message_type() {
+        for value in 1u8..=255 {
+            let (_, message_type) = parse_dhcpv6_message_type(&[value][..]).unwrap();
+            let mut output = Vec::new();
+            encode_dhcpv6_message_type(&message_type, &mut output);
+            assert_eq!(output, vec![value]);
+        }
+    }
+
+    #[test]
+    fn test_extended_dhcpv6_message_type() {
+        assert_eq!(
+            parse_dhcpv6_message_type(&[14u8][..]),
+            Ok((&b""[..], DHCPv6MessageType::Leasequery))
+        );
+        assert_eq!(
+            parse_dhcpv6_message_type(&[20u8][..]),
+            Ok((&b""[..], DHCPv6MessageType::DHCPv4Query))
+        );
+        assert_eq!(
+            parse_dhcpv6_message_type(&[23u8][..]),
+            Ok((&b""[..], DHCPv6MessageType::StartTLS))
+        );
+        assert_eq!(
+            parse_dhcpv6_message_type(&[35u8][..]),
+            Ok((&b""[..], DHCPv6MessageType::Contact))
+        );
+        assert_eq!(
+            parse_dhcpv6_message_type(&[37u8][..]),
+            Ok((&b""[..], DHCPv6MessageType::AddrRegReply))
+        );
+        assert_eq!(
+            parse_dhcpv6_message_type(&[38u8][..]),
+            Ok((&b""[..], DHCPv6MessageType::Unassigned(38)))
+        );
+        assert_eq!(DHCPv6MessageType::Unassigned(1), DHCPv6MessageType::Solicit);
+        assert_ne!(
+            DHCPv6MessageType::Unassigned(38),
+            DHCPv6MessageType::Unassigned(39)
+        );
+    }
+
     #[test]
     fn test_invalid_dhcpv6_message_type() {
         assert!(parse_dhcpv6_message_type(&[0u8][..]).is_err());
-        assert!(parse_dhcpv6_message_type(&[14u8][..]).is_err());
+    }
+
+    #[test]
+    fn test_dhcpv6_message_type_errors() {
+        assert_eq!(
+            parse_dhcpv6_message_type(&[0u8][..]).unwrap_err(),
+            nom::Err::Error(ParseError::new(
+                &[0u8][..],
+                ErrorKind::UnknownMessageType(0)
+            ))
+        );
+        assert_eq!(
+            parse_dhcpv6_message_type(&[][..]).unwrap_err(),
+            nom::Err::Error(ParseError::new(&[][..], ErrorKind::TruncatedHeader))
+        );
     }
 }
@@ -1,3 +1,40 @@
+// Registry enums have a catch-all variant (Unassigned or Other) that can also
+// hold a value with a named variant. They compare and hash by wire value, so
+// that OptionCode::Unassigned(1) is the same code as OptionCode::ClientId.
+macro_rules! impl_eq_by_value {
+    ($type:ident, $to_value:ident) => {
+        impl PartialEq for $type {
+            fn eq(&self, other: &Self) -> bool {
+                self.$to_value() == other.$to_value()
+            }
+        }
+
+        impl Eq for $type {}
+
+        impl core::hash::Hash for $type {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                core::hash::Hash::hash(&self.$to_value(), state);
+            }
+        }
+    };
+}
+
+#[cfg(feature = "alloc")]
+pub mod domain_name;
+#[cfg(feature = "alloc")]
+pub mod duid;
+#[cfg(feature = "alloc")]
 pub mod header;
+pub mod interface_id;
+pub mod link_layer;
 pub mod message_types;
+#[cfg(feature = "alloc")]
+pub mod ntp;
+pub mod option_codes;
+#[cfg(feature = "alloc")]
 pub mod options;
+pub mod options_iter;
+#[cfg(feature = "alloc")]
+pub mod relay;
+pub mod status_code;
+pub mod timers;
@@ -0,0 +1,258 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::net::Ipv6Addr;
+use nom::bytes::complete::take;
+use nom::number::complete::be_u16;
+use nom::sequence::tuple;
+
+use crate::encode::{check_length, EncodeError, Sink};
+use crate::error::{fail, with_kind, ErrorKind, ParseError, ParseResult};
+use crate::structs::domain_name::{
+    domain_name_len, encode_domain_name, parse_domain_name, DomainName,
+};
+use crate::structs::option_codes::OptionCode;
+use crate::utils::{encode_ipv6_address, parse_ipv6_address};
+
+// RFC 5908 section 4.
+const NTP_SUBOPTION_SRV_ADDR: u16 = 1;
+const NTP_SUBOPTION_MC_ADDR: u16 = 2;
+const NTP_SUBOPTION_SRV_FQDN: u16 = 3;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum NtpSuboption<'a> {
+    ServerAddress { address: Ipv6Addr },
+    MulticastAddress { address: Ipv6Addr },
+    ServerFqdn { name: DomainName<'a> },
+    Unknown { code: u16, data: Cow<'a, [u8]> },
+}
+
+impl<'a> NtpSuboption<'a> {
+    pub fn code(&self) -> u16 {
+        match self {
+            NtpSuboption::ServerAddress { .. } => NTP_SUBOPTION_SRV_ADDR,
+            NtpSuboption::MulticastAddress { .. } => NTP_SUBOPTION_MC_ADDR,
+            NtpSuboption::ServerFqdn { .. } => NTP_SUBOPTION_SRV_FQDN,
+            NtpSuboption::Unknown { code, .. } => *code,
+        }
+    }
+
+    pub fn into_owned(self) -> NtpSuboption<'static> {
+        match self {
+            NtpSuboption::ServerAddress { address } => NtpSuboption::ServerAddress { address },
+            NtpSuboption::MulticastAddress { address } => {
+                NtpSuboption::MulticastAddress { address }
+            }
+            NtpSuboption::ServerFqdn { name } => NtpSuboption::ServerFqdn {
+                name: name.into_owned(),
+            },
+            NtpSuboption::Unknown { code, data } => NtpSuboption::Unknown {
+                code,
+                data: Cow::Owned(data.into_owned()),
+            },
+        }
+    }
+
+    pub fn as_borrowed(&self) -> NtpSuboption<'_> {
+        match self {
+            NtpSuboption::ServerAddress { address } => {
+                NtpSuboption::ServerAddress { address: *address }
+            }
+            NtpSuboption::MulticastAddress { address } => {
+                NtpSuboption::MulticastAddress { address: *address }
+            }
+            NtpSuboption::ServerFqdn { name } => NtpSuboption::ServerFqdn {
+                name: name.as_borrowed(),
+            },
+            NtpSuboption::Unknown { code, data } => NtpSuboption::Unknown {
+                code: *code,
+                data: Cow::Borrowed(data),
+            },
+        }
+    }
+}
+
+fn parse_ntp_suboption_value(code: u16, input: &[u8]) -> ParseResult<'_, NtpSuboption<'_>> {
+    match code {
+        NTP_SUBOPTION_SRV_ADDR | NTP_SUBOPTION_MC_ADDR => {
+            if input.len() != 16 {
+                let kind = ErrorKind::BadSuboptionLength {
+                    option: OptionCode::NtpServer.to_u16(),
+                    code,
+                    length: input.len() as u16,
+                };
+                return fail(input, kind);
+            }
+            let (rest, address) = parse_ipv6_address(input)?;
+            let suboption = if code == NTP_SUBOPTION_SRV_ADDR {
+                NtpSuboption::ServerAddress { address }
+            } else {
+                NtpSuboption::MulticastAddress { address }
+            };
+
+            Ok((rest, suboption))
+        }
+        NTP_SUBOPTION_SRV_FQDN => {
+            let (rest, name) = parse_domain_name(input)?;
+            if !rest.is_empty() || !name.is_fully_qualified() {
+                return fail(input, ErrorKind::InvalidDomainName);
+            }
+
+            Ok((rest, NtpSuboption::ServerFqdn { name }))
+        }
+        _ => Ok((
+            &input[input.len()..],
+            NtpSuboption::Unknown {
+                code,
+                data: Cow::Borrowed(input),
+            },
+        )),
+    }
+}
+
+pub fn parse_ntp_suboption(input: &[u8]) -> ParseResult<'_, NtpSuboption<'_>> {
+    let (rest, (code, len)) =
+        with_kind(ErrorKind::TruncatedOption, tuple((be_u16, be_u16)))(input)?;
+    let (rest, value) = take::<_, _, ParseError<&[u8]>>(len as usize)(rest).map_err(|_| {
+        nom::Err::Error(ParseError::new(input, ErrorKind::TruncatedOption).in_option(code))
+    })?;
+    let (_, suboption) =
+        parse_ntp_suboption_value(code, value).map_err(|e| e.map(|e| e.in_option(code)))?;
+
+    Ok((rest, suboption))
+}
+
+pub fn parse_ntp_suboptions(input: &[u8]) -> ParseResult<'_, Vec<NtpSuboption<'_>>> {
+    let mut suboptions = Vec::new();
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        let (next, suboption) = parse_ntp_suboption(rest)?;
+        suboptions.push(suboption);
+        rest = next;
+    }
+
+    Ok((rest, suboptions))
+}
+
+fn ntp_suboption_value_len(suboption: &NtpSuboption) -> usize {
+    match suboption {
+        NtpSuboption::ServerAddress { .. } | NtpSuboption::MulticastAddress { .. } => 16,
+        NtpSuboption::ServerFqdn { name } => domain_name_len(name),
+        NtpSuboption::Unknown { data, .. } => data.len(),
+    }
+}
+
+pub fn ntp_suboptions_len(suboptions: &[NtpSuboption]) -> usize {
+    suboptions
+        .iter()
+        .map(|suboption| 4 + ntp_suboption_value_len(suboption))
+        .sum()
+}
+
+pub fn encode_ntp_suboptions<S: Sink>(
+    suboptions: &[NtpSuboption],
+    output: &mut S,
+) -> Result<(), EncodeError> {
+    for suboption in suboptions {
+        let code = suboption.code();
+        let len = check_length(code, ntp_suboption_value_len(suboption))?;
+        output.put(&code.to_be_bytes());
+        output.put(&len.to_be_bytes());
+
+        match suboption {
+            NtpSuboption::ServerAddress { address }
+            | NtpSuboption::MulticastAddress { address } => encode_ipv6_address(address, output),
+            NtpSuboption::ServerFqdn { name } => encode_domain_name(name, output),
+            NtpSuboption::Unknown { data, .. } => output.put(data),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ntp_suboptions() {
+        let input = b"\x00\x01\x00\x10\x20\x01\x0d\xb8\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x01\
+                      \x00\x02\x00\x10\xff\x02\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x01\x01\
+                      \x00\x03\x00\x0d\x03ntp\x07example\x00\
+                      \x00\x09\x00\x02ab";
+        let (rest, suboptions) = parse_ntp_suboptions(&input[..]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(suboptions.len(), 4);
+        assert_eq!(
+            suboptions[0],
+            NtpSuboption::ServerAddress {
+                address: "2001:db8::1".parse().unwrap()
+            }
+        );
+        assert_eq!(
+            suboptions[1],
+            NtpSuboption::MulticastAddress {
+                address: "ff02::101".parse().unwrap()
+            }
+        );
+        match &suboptions[2] {
+            NtpSuboption::ServerFqdn { name } => assert_eq!(name.to_string(), "ntp.example."),
+            suboption => panic!("unexpected suboption {:?}", suboption),
+        }
+        assert_eq!(
+            suboptions[3],
+            NtpSuboption::Unknown {
+                code: 9,
+                data: Cow::Borrowed(&b"ab"[..])
+            }
+        );
+        assert_eq!(ntp_suboptions_len(&suboptions), input.len());
+
+        let mut output = Vec::new();
+        encode_ntp_suboptions(&suboptions, &mut output).unwrap();
+        assert_eq!(&output[..], &input[..]);
+    }
+
+    #[test]
+    fn test_encode_ntp_server_fqdn() {
+        let suboptions = vec![NtpSuboption::ServerFqdn {
+            name: DomainName::from_labels(&["ntp", "example"], true).unwrap(),
+        }];
+        let mut output = Vec::new();
+        encode_ntp_suboptions(&suboptions, &mut output).unwrap();
+        assert_eq!(&output[..], &b"\x00\x03\x00\x0d\x03ntp\x07example\x00"[..]);
+        assert_eq!(ntp_suboptions_len(&suboptions), output.len());
+        assert_eq!(
+            parse_ntp_suboptions(&output[..]),
+            Ok((&b""[..], suboptions))
+        );
+    }
+
+    #[test]
+    fn test_invalid_ntp_suboptions() {
+        match parse_ntp_suboptions(&b"\x00\x01\x00\x04\x7f\x00\x00\x01"[..]).unwrap_err() {
+            nom::Err::Error(e) => {
+                assert_eq!(
+                    e.kind,
+                    ErrorKind::BadSuboptionLength {
+                        option: 56,
+                        code: 1,
+                        length: 4
+                    }
+                );
+                assert_eq!(e.path, vec![1]);
+            }
+            e => panic!("unexpected error {:?}", e),
+        }
+
+        match parse_ntp_suboptions(&b"\x00\x03\x00\x04\x03ntp"[..]).unwrap_err() {
+            nom::Err::Error(e) => assert_eq!(e.kind, ErrorKind::InvalidDomainName),
+            e => panic!("unexpected error {:?}", e),
+        }
+
+        match parse_ntp_suboptions(&b"\x00\x02\x00\x10\xff\x02"[..]).unwrap_err() {
+            nom::Err::Error(e) => assert_eq!(e.kind, ErrorKind::TruncatedOption),
+            e => panic!("unexpected error {:?}", e),
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use core::fmt;
+
+macro_rules! option_codes {
+    ($($name:ident = $code:expr, $mnemonic:expr, $reference:expr;)*) => {
+        #[derive(Debug, Clone, Copy)]
+        pub enum OptionCode {
+            $($name,)*
+            Unassigned(u16),
+        }
+
+        impl OptionCode {
+            pub fn from_u16(value: u16) -> Self {
+                match value {
+                    $($code => Self::$name,)*
+                    _ => Self::Unassigned(value),
+                }
+            }
+
+            pub fn to_u16(self) -> u16 {
+                match self {
+                    $(Self::$name => $code,)*
+                    Self::Unassigned(value) => value,
+                }
+            }
+
+            pub fn mnemonic(self) -> Option<&'static str> {
+                match Self::from_u16(self.to_u16()) {
+                    $(Self::$name => Some($mnemonic),)*
+                    Self::Unassigned(_) => None,
+                }
+            }
+
+            pub fn reference(self) -> Option<&'static str> {
+                match Self::from_u16(self.to_u16()) {
+                    $(Self::$name => Some($reference),)*
+                    Self::Unassigned(_) => None,
+                }
+            }
+        }
+    };
+}
+
+option_codes! {
+    ClientId = 1, "OPTION_CLIENTID", "RFC 8415";
+    ServerId = 2, "OPTION_SERVERID", "RFC 8415";
+    IaNa = 3, "OPTION_IA_NA", "RFC 8415";
+    IaTa = 4, "OPTION_IA_TA", "RFC 8415";
+    IaAddr = 5, "OPTION_IAADDR", "RFC 8415";
+    OptionRequest = 6, "OPTION_ORO", "RFC 8415";
+    Preference = 7, "OPTION_PREFERENCE", "RFC 8415";
+    ElapsedTime = 8, "OPTION_ELAPSED_TIME", "RFC 8415";
+    RelayMessage = 9, "OPTION_RELAY_MSG", "RFC 8415";
+    Authentication = 11, "OPTION_AUTH", "RFC 8415";
+    ServerUnicast = 12, "OPTION_UNICAST", "RFC 8415";
+    StatusCode = 13, "OPTION_STATUS_CODE", "RFC 8415";
+    RapidCommit = 14, "OPTION_RAPID_COMMIT", "RFC 8415";
+    UserClass = 15, "OPTION_USER_CLASS", "RFC 8415";
+    VendorClass = 16, "OPTION_VENDOR_CLASS", "RFC 8415";
+    VendorSpecificInformation = 17, "OPTION_VENDOR_OPTS", "RFC 8415";
+    InterfaceId = 18, "OPTION_INTERFACE_ID", "RFC 8415";
+    ReconfigureMessage = 19, "OPTION_RECONF_MSG", "RFC 8415";
+    ReconfigureAccept = 20, "OPTION_RECONF_ACCEPT", "RFC 8415";
+    SipServerDomainNames = 21, "OPTION_SIP_SERVER_D", "RFC 3319";
+    SipServerAddresses = 22, "OPTION_SIP_SERVER_A", "RFC 3319";
+    DnsServers = 23, "OPTION_DNS_SERVERS", "RFC 3646";
+    DomainSearchList = 24, "OPTION_DOMAIN_LIST", "RFC 3646";
+    IaPd = 25, "OPTION_IA_PD", "RFC 8415";
+    IaPrefix = 26, "OPTION_IAPREFIX", "RFC 8415";
+    NisServers = 27, "OPTION_NIS_SERVERS", "RFC 3898";
+    NispServers = 28, "OPTION_NISP_SERVERS", "RFC 3898";
+    NisDomainName = 29, "OPTION_NIS_DOMAIN_NAME", "RFC 3898";
+    NispDomainName = 30, "OPTION_NISP_DOMAIN_NAME", "RFC 3898";
+    SntpServers = 31, "OPTION_SNTP_SERVERS", "RFC 4075";
+    InformationRefreshTime = 32, "OPTION_INFORMATION_REFRESH_TIME", "RFC 8415";
+    BcmcsServerDomainNames = 33, "OPTION_BCMCS_SERVER_D", "RFC 4280";
+    BcmcsServerAddresses = 34, "OPTION_BCMCS_SERVER_A", "RFC 4280";
+    GeoconfCivic = 36, "OPTION_GEOCONF_CIVIC", "RFC 4776";
+    RemoteId = 37, "OPTION_REMOTE_ID", "RFC 4649";
+    SubscriberId = 38, "OPTION_SUBSCRIBER_ID", "RFC 4580";
+    ClientFqdn = 39, "OPTION_CLIENT_FQDN", "RFC 4704";
+    PanaAgent = 40, "OPTION_PANA_AGENT", "RFC 5192";
+    NewPosixTimezone = 41, "OPTION_NEW_POSIX_TIMEZONE", "RFC 4833";
+    NewTzdbTimezone = 42, "OPTION_NEW_TZDB_TIMEZONE", "RFC 4833";
+    EchoRequest = 43, "OPTION_ERO", "RFC 4994";
+    LqQuery = 44, "OPTION_LQ_QUERY", "RFC 5007";
+    ClientData = 45, "OPTION_CLIENT_DATA", "RFC 5007";
+    ClientLastTransactionTime = 46, "OPTION_CLT_TIME", "RFC 5007";
+    LqRelayData = 47, "OPTION_LQ_RELAY_DATA", "RFC 5007";
+    LqClientLink = 48, "OPTION_LQ_CLIENT_LINK", "RFC 5007";
+    Mip6HomeNetworkIdFqdn = 49, "OPTION_MIP6_HNIDF", "RFC 6610";
+    Mip6VisitedHomeNetworkInformation = 50, "OPTION_MIP6_VDINF", "RFC 6610";
+    V6Lost = 51, "OPTION_V6_LOST", "RFC 5223";
+    CapwapAccessControllerV6 = 52, "OPTION_CAPWAP_AC_V6", "RFC 5417";
+    RelayId = 53, "OPTION_RELAY_ID", "RFC 5460";
+    MosAddresses = 54, "OPTION-IPv6_Address-MoS", "RFC 5678";
+    MosDomainNames = 55, "OPTION-IPv6_FQDN-MoS", "RFC 5678";
+    NtpServer = 56, "OPTION_NTP_SERVER", "RFC 5908";
+    V6AccessDomain = 57, "OPTION_V6_ACCESS_DOMAIN", "RFC 5986";
+    SipUaConfigServiceDomains = 58, "OPTION_SIP_UA_CS_LIST", "RFC 6011";
+    BootfileUrl = 59, "OPT_BOOTFILE_URL", "RFC 5970";
+    BootfileParameters = 60, "OPT_BOOTFILE_PARAM", "RFC 5970";
+    ClientArchitectureType = 61, "OPTION_CLIENT_ARCH_TYPE", "RFC 5970";
+    NetworkInterfaceIdentifier = 62, "OPTION_NII", "RFC 5970";
+    Geolocation = 63, "OPTION_GEOLOCATION", "RFC 6225";
+    AftrName = 64, "OPTION_AFTR_NAME", "RFC 6334";
+    ErpLocalDomainName = 65, "OPTION_ERP_LOCAL_DOMAIN_NAME", "RFC 6440";
+    RelaySuppliedOptions = 66, "OPTION_RSOO", "RFC 6422";
+    PdExclude = 67, "OPTION_PD_EXCLUDE", "RFC 6603";
+    VirtualSubnetSelection = 68, "OPTION_VSS", "RFC 6607";
+    Mip6IdentifiedHomeNetworkInformation = 69, "OPTION_MIP6_IDINF", "RFC 6610";
+    Mip6UnrestrictedHomeNetworkInformation = 70, "OPTION_MIP6_UDINF", "RFC 6610";
+    Mip6HomeNetworkPrefix = 71, "OPTION_MIP6_HNP", "RFC 6610";
+    Mip6HomeAgentAddress = 72, "OPTION_MIP6_HAA", "RFC 6610";
+    Mip6HomeAgentFqdn = 73, "OPTION_MIP6_HAF", "RFC 6610";
+    RdnssSelection = 74, "OPTION_RDNSS_SELECTION", "RFC 6731";
+    KrbPrincipalName = 75, "OPTION_KRB_PRINCIPAL_NAME", "RFC 6784";
+    KrbRealmName = 76, "OPTION_KRB_REALM_NAME", "RFC 6784";
+    KrbDefaultRealmName = 77, "OPTION_KRB_DEFAULT_REALM_NAME", "RFC 6784";
+    KrbKdc = 78, "OPTION_KRB_KDC", "RFC 6784";
+    ClientLinkLayerAddress = 79, "OPTION_CLIENT_LINKLAYER_ADDR", "RFC 6939";
+    LinkAddress = 80, "OPTION_LINK_ADDRESS", "RFC 6977";
+    Radius = 81, "OPTION_RADIUS", "RFC 7037";
+    SolMaxRt = 82, "OPTION_SOL_MAX_RT", "RFC 8415";
+    InfMaxRt = 83, "OPTION_INF_MAX_RT", "RFC 8415";
+    AddressSelection = 84, "OPTION_ADDRSEL", "RFC 7078";
+    AddressSelectionTable = 85, "OPTION_ADDRSEL_TABLE", "RFC 7078";
+    V6PcpServer = 86, "OPTION_V6_PCP_SERVER", "RFC 7291";
+    DHCPv4Message = 87, "OPTION_DHCPV4_MSG", "RFC 7341";
+    DHCPv4OverDHCPv6Server = 88, "OPTION_DHCP4_O_DHCP6_SERVER", "RFC 7341";
+    S46Rule = 89, "OPTION_S46_RULE", "RFC 7598";
+    S46BorderRelay = 90, "OPTION_S46_BR", "RFC 7598";
+    S46DefaultMappingRule = 91, "OPTION_S46_DMR", "RFC 7598";
+    S46V4V6Binding = 92, "OPTION_S46_V4V6BIND", "RFC 7598";
+    S46PortParameters = 93, "OPTION_S46_PORTPARAMS", "RFC 7598";
+    S46MapEContainer = 94, "OPTION_S46_CONT_MAPE", "RFC 7598";
+    S46MapTContainer = 95, "OPTION_S46_CONT_MAPT", "RFC 7598";
+    S46LightweightContainer = 96, "OPTION_S46_CONT_LW", "RFC 7598";
+    FourRd = 97, "OPTION_4RD", "RFC 7600";
+    FourRdMapRule = 98, "OPTION_4RD_MAP_RULE", "RFC 7600";
+    FourRdNonMapRule = 99, "OPTION_4RD_NON_MAP_RULE", "RFC 7600";
+    LqBaseTime = 100, "OPTION_LQ_BASE_TIME", "RFC 7653";
+    LqStartTime = 101, "OPTION_LQ_START_TIME", "RFC 7653";
+    LqEndTime = 102, "OPTION_LQ_END_TIME", "RFC 7653";
+    CaptivePortal = 103, "OPTION_V6_CAPTIVE_PORTAL", "RFC 8910";
+    MplParameters = 104, "OPTION_MPL_PARAMETERS", "RFC 7774";
+    AniAccessTechnologyType = 105, "OPTION_ANI_ATT", "RFC 7839";
+    AniNetworkName = 106, "OPTION_ANI_NETWORK_NAME", "RFC 7839";
+    AniAccessPointName = 107, "OPTION_ANI_AP_NAME", "RFC 7839";
+    AniAccessPointBssid = 108, "OPTION_ANI_AP_BSSID", "RFC 7839";
+    AniOperatorId = 109, "OPTION_ANI_OPERATOR_ID", "RFC 7839";
+    AniOperatorRealm = 110, "OPTION_ANI_OPERATOR_REALM", "RFC 7839";
+    S46Priority = 111, "OPTION_S46_PRIORITY", "RFC 8026";
+    MudUrlV6 = 112, "OPTION_MUD_URL_V6", "RFC 8520";
+    V6Prefix64 = 113, "OPTION_V6_PREFIX64", "RFC 8115";
+    FailoverBindingStatus = 114, "OPTION_F_BINDING_STATUS", "RFC 8156";
+    FailoverConnectFlags = 115, "OPTION_F_CONNECT_FLAGS", "RFC 8156";
+    FailoverDnsRemovalInfo = 116, "OPTION_F_DNS_REMOVAL_INFO", "RFC 8156";
+    FailoverDnsHostName = 117, "OPTION_F_DNS_HOST_NAME", "RFC 8156";
+    FailoverDnsZoneName = 118, "OPTION_F_DNS_ZONE_NAME", "RFC 8156";
+    FailoverDnsFlags = 119, "OPTION_F_DNS_FLAGS", "RFC 8156";
+    FailoverExpirationTime = 120, "OPTION_F_EXPIRATION_TIME", "RFC 8156";
+    FailoverMaxUnackedBindingUpdates = 121, "OPTION_F_MAX_UNACKED_BNDUPD", "RFC 8156";
+    FailoverMaximumClientLeadTime = 122, "OPTION_F_MCLT", "RFC 8156";
+    FailoverPartnerLifetime = 123, "OPTION_F_PARTNER_LIFETIME", "RFC 8156";
+    FailoverPartnerLifetimeSent = 124, "OPTION_F_PARTNER_LIFETIME_SENT", "RFC 8156";
+    FailoverPartnerDownTime = 125, "OPTION_F_PARTNER_DOWN_TIME", "RFC 8156";
+    FailoverPartnerRawClientLastTransactionTime = 126, "OPTION_F_PARTNER_RAW_CLT_TIME", "RFC 8156";
+    FailoverProtocolVersion = 127, "OPTION_F_PROTOCOL_VERSION", "RFC 8156";
+    FailoverKeepaliveTime = 128, "OPTION_F_KEEPALIVE_TIME", "RFC 8156";
+    FailoverReconfigureData = 129, "OPTION_F_RECONFIGURE_DATA", "RFC 8156";
+    FailoverRelationshipName = 130, "OPTION_F_RELATIONSHIP_NAME", "RFC 8156";
+    FailoverServerFlags = 131, "OPTION_F_SERVER_FLAGS", "RFC 8156";
+    FailoverServerState = 132, "OPTION_F_SERVER_STATE", "RFC 8156";
+    FailoverStartTimeOfState = 133, "OPTION_F_START_TIME_OF_STATE", "RFC 8156";
+    FailoverStateExpirationTime = 134, "OPTION_F_STATE_EXPIRATION_TIME", "RFC 8156";
+    RelayPort = 135, "OPTION_RELAY_PORT", "RFC 8357";
+    V6SztpRedirect = 136, "OPTION_V6_SZTP_REDIRECT", "RFC 8572";
+    S46BindIpv6Prefix = 137, "OPTION_S46_BIND_IPV6_PREFIX", "RFC 8539";
+    IaLl = 138, "OPTION_IA_LL", "RFC 8947";
+    LinkLayerAddress = 139, "OPTION_LLADDR", "RFC 8947";
+    SlapQuadrant = 140, "OPTION_SLAP_QUAD", "RFC 8948";
+    V6DotsRi = 141, "OPTION_V6_DOTS_RI", "RFC 8973";
+    V6DotsAddress = 142, "OPTION_V6_DOTS_ADDRESS", "RFC 8973";
+    AndsfAddresses = 143, "OPTION-IPv6_Address-ANDSF", "RFC 6153";
+    V6Dnr = 144, "OPTION_V6_DNR", "RFC 9463";
+    RegisteredDomain = 145, "OPTION_REGISTERED_DOMAIN", "RFC 9527";
+    ForwardDistManager = 146, "OPTION_FORWARD_DIST_MANAGER", "RFC 9527";
+    ReverseDistManager = 147, "OPTION_REVERSE_DIST_MANAGER", "RFC 9527";
+    AddrRegEnable = 148, "OPTION_ADDR_REG_ENABLE", "RFC 9686";
+}
+
+impl_eq_by_value!(OptionCode, to_u16);
+
+impl fmt::Display for OptionCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.mnemonic() {
+            Some(mnemonic) => f.write_str(mnemonic),
+            None => write!(f, "OPTION_{}", self.to_u16()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_option_code_roundtrip() {
+        for value in 0..=u16::MAX {
+            assert_eq!(OptionCode::from_u16(value).to_u16(), value);
+        }
+    }
+
+    #[test]
+    fn test_option_code_registry() {
+        assert_eq!(OptionCode::from_u16(1), OptionCode::ClientId);
+        assert_eq!(OptionCode::from_u16(10), OptionCode::Unassigned(10));
+        assert_eq!(OptionCode::from_u16(148), OptionCode::AddrRegEnable);
+        assert_eq!(OptionCode::from_u16(149), OptionCode::Unassigned(149));
+        assert_eq!(OptionCode::IaPd.mnemonic(), Some("OPTION_IA_PD"));
+        assert_eq!(OptionCode::ClientFqdn.reference(), Some("RFC 4704"));
+        assert_eq!(OptionCode::Unassigned(35).reference(), None);
+        assert_eq!(OptionCode::SolMaxRt.to_string(), "OPTION_SOL_MAX_RT");
+        assert_eq!(OptionCode::Unassigned(4242).to_string(), "OPTION_4242");
+        assert_eq!(OptionCode::Unassigned(1), OptionCode::ClientId);
+        assert_eq!(OptionCode::Unassigned(1).to_string(), "OPTION_CLIENTID");
+        assert_ne!(OptionCode::Unassigned(10), OptionCode::Unassigned(35));
+    }
+}
@@ -1,37 +1,54 @@
-// use nom::{be_u16, be_u32, be_u64, be_u8, IResult};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::net::Ipv6Addr;
+use core::time::Duration;
 use nom::bytes::complete::take;
-use nom::combinator::verify;
-use nom::multi::{many0, many_m_n};
-use nom::number::complete::{be_u16, be_u32, be_u64};
+use nom::combinator::rest;
+use nom::number::complete::{be_u16, be_u32, be_u64, be_u8};
 use nom::sequence::tuple;
-use nom::IResult;
-use std::net::Ipv6Addr;
 
-use crate::utils::parse_ipv6_address;
+use crate::config::{LengthMismatch, ParseConfig, TrailingBytes, UnknownOptions, Utf8Policy};
+use crate::encode::{check_length, EncodeError, Sink};
+use crate::error::{fail, with_kind, ErrorKind, ParseError, ParseResult};
+use crate::structs::domain_name::{
+    domain_name_len, domain_name_list_len, encode_domain_name, encode_domain_name_list,
+    parse_domain_name, parse_domain_name_list, DomainName,
+};
+use crate::structs::duid::{duid_len, encode_duid, parse_duid, Duid};
+use crate::structs::link_layer::HardwareType;
+use crate::structs::ntp::{
+    encode_ntp_suboptions, ntp_suboptions_len, parse_ntp_suboptions, NtpSuboption,
+};
+use crate::structs::option_codes::OptionCode;
+use crate::structs::status_code::StatusCode;
+use crate::structs::timers::INFINITY;
+use crate::utils::{encode_ipv6_address, parse_ipv6_address};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum DHCPv6Option<'a> {
     CliendID {
-        duid: &'a [u8],
+        duid: Duid<'a>,
     },
     ServerID {
-        duid: &'a [u8],
+        duid: Duid<'a>,
     },
     IdentityAssociationForNonTemporaryAddresses {
         id: u32,
         time_1: u32,
         time_2: u32,
-        options: &'a [u8],
+        options: Vec<DHCPv6Option<'a>>,
     },
     IdentityAssociationForTemporaryAddresses {
         id: u32,
-        options: &'a [u8],
+        options: Vec<DHCPv6Option<'a>>,
     },
     IdentityAssociationAddress {
         address: Ipv6Addr,
         prefered_lifetime: u32,
         valid_lifetime: u32,
-        options: &'a [u8],
+        options: Vec<DHCPv6Option<'a>>,
     },
     OptionRequest {
         options: Vec<u16>,
@@ -43,61 +60,599 @@ pub enum DHCPv6Option<'a> {
         elapsed_time: u16,
     },
     RelayMessage {
-        data: &'a [u8],
+        data: Cow<'a, [u8]>,
     },
     Authentication {
         protocol: u8,
         algorithm: u8,
         rdm: u8,
         replay_detection: u64,
-        authentication_information: &'a [u8],
+        authentication_information: Cow<'a, [u8]>,
     },
     ServerUnicast {
         address: Ipv6Addr,
     },
     StatusCode {
-        code: u16,
-        message: &'a str,
+        code: StatusCode,
+        message: Cow<'a, [u8]>,
     },
     RapidCommit {},
     UserClass {
-        data: &'a [u8],
+        data: Cow<'a, [u8]>,
     },
     VendorClass {
         enterprise_number: u32,
-        data: &'a [u8],
+        data: Cow<'a, [u8]>,
     },
     VendorSpecificInformation {
         enterprise_number: u32,
-        data: &'a [u8],
+        data: Cow<'a, [u8]>,
     },
     InterfaceID {
-        data: &'a [u8],
+        data: Cow<'a, [u8]>,
+    },
+    RemoteID {
+        enterprise_number: u32,
+        data: Cow<'a, [u8]>,
+    },
+    SubscriberID {
+        data: Cow<'a, [u8]>,
     },
     ReconfigureMessage {
         message_type: u8,
     },
     ReconfigureAccept {},
+    IdentityAssociationForPrefixDelegation {
+        id: u32,
+        time_1: u32,
+        time_2: u32,
+        options: Vec<DHCPv6Option<'a>>,
+    },
+    IdentityAssociationPrefix {
+        prefered_lifetime: u32,
+        valid_lifetime: u32,
+        prefix_length: u8,
+        prefix: Ipv6Addr,
+        options: Vec<DHCPv6Option<'a>>,
+    },
+    DnsServers {
+        servers: Vec<Ipv6Addr>,
+    },
+    DomainSearchList {
+        domains: Vec<DomainName<'a>>,
+    },
+    ClientFqdn {
+        server_update: bool,
+        server_override: bool,
+        no_update: bool,
+        mbz_flags: u8,
+        domain_name: DomainName<'a>,
+    },
+    ClientLinkLayerAddress {
+        hardware_type: HardwareType,
+        link_layer_address: Cow<'a, [u8]>,
+    },
+    InformationRefreshTime {
+        refresh_time: Duration,
+    },
+    SolMaxRt {
+        sol_max_rt: Duration,
+    },
+    InfMaxRt {
+        inf_max_rt: Duration,
+    },
+    SntpServers {
+        servers: Vec<Ipv6Addr>,
+    },
+    NtpServer {
+        suboptions: Vec<NtpSuboption<'a>>,
+    },
+    Unknown {
+        code: u16,
+        data: Cow<'a, [u8]>,
+    },
+    Malformed {
+        code: u16,
+        data: Cow<'a, [u8]>,
+    },
 }
 
-fn parse_dhcpv6_option_client_id(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, len) = be_u16(input)?;
-    let (rest, duid) = take(len as usize)(rest)?;
+impl<'a> DHCPv6Option<'a> {
+    pub fn code(&self) -> OptionCode {
+        match self {
+            DHCPv6Option::CliendID { .. } => OptionCode::ClientId,
+            DHCPv6Option::ServerID { .. } => OptionCode::ServerId,
+            DHCPv6Option::IdentityAssociationForNonTemporaryAddresses { .. } => OptionCode::IaNa,
+            DHCPv6Option::IdentityAssociationForTemporaryAddresses { .. } => OptionCode::IaTa,
+            DHCPv6Option::IdentityAssociationAddress { .. } => OptionCode::IaAddr,
+            DHCPv6Option::OptionRequest { .. } => OptionCode::OptionRequest,
+            DHCPv6Option::Preference { .. } => OptionCode::Preference,
+            DHCPv6Option::ElapstedTime { .. } => OptionCode::ElapsedTime,
+            DHCPv6Option::RelayMessage { .. } => OptionCode::RelayMessage,
+            DHCPv6Option::Authentication { .. } => OptionCode::Authentication,
+            DHCPv6Option::ServerUnicast { .. } => OptionCode::ServerUnicast,
+            DHCPv6Option::StatusCode { .. } => OptionCode::StatusCode,
+            DHCPv6Option::RapidCommit {} => OptionCode::RapidCommit,
+            DHCPv6Option::UserClass { .. } => OptionCode::UserClass,
+            DHCPv6Option::VendorClass { .. } => OptionCode::VendorClass,
+            DHCPv6Option::VendorSpecificInformation { .. } => OptionCode::VendorSpecificInformation,
+            DHCPv6Option::InterfaceID { .. } => OptionCode::InterfaceId,
+            DHCPv6Option::RemoteID { .. } => OptionCode::RemoteId,
+            DHCPv6Option::SubscriberID { .. } => OptionCode::SubscriberId,
+            DHCPv6Option::ReconfigureMessage { .. } => OptionCode::ReconfigureMessage,
+            DHCPv6Option::ReconfigureAccept {} => OptionCode::ReconfigureAccept,
+            DHCPv6Option::DnsServers { .. } => OptionCode::DnsServers,
+            DHCPv6Option::DomainSearchList { .. } => OptionCode::DomainSearchList,
+            DHCPv6Option::ClientFqdn { .. } => OptionCode::ClientFqdn,
+            DHCPv6Option::ClientLinkLayerAddress { .. } => OptionCode::ClientLinkLayerAddress,
+            DHCPv6Option::InformationRefreshTime { .. } => OptionCode::InformationRefreshTime,
+            DHCPv6Option::SolMaxRt { .. } => OptionCode::SolMaxRt,
+            DHCPv6Option::InfMaxRt { .. } => OptionCode::InfMaxRt,
+            DHCPv6Option::SntpServers { .. } => OptionCode::SntpServers,
+            DHCPv6Option::NtpServer { .. } => OptionCode::NtpServer,
+            DHCPv6Option::IdentityAssociationForPrefixDelegation { .. } => OptionCode::IaPd,
+            DHCPv6Option::IdentityAssociationPrefix { .. } => OptionCode::IaPrefix,
+            DHCPv6Option::Unknown { code, .. } | DHCPv6Option::Malformed { code, .. } => {
+                OptionCode::from_u16(*code)
+            }
+        }
+    }
+
+    pub fn options(&self) -> &[DHCPv6Option<'a>] {
+        match self {
+            DHCPv6Option::IdentityAssociationForNonTemporaryAddresses { options, .. }
+            | DHCPv6Option::IdentityAssociationForTemporaryAddresses { options, .. }
+            | DHCPv6Option::IdentityAssociationAddress { options, .. }
+            | DHCPv6Option::IdentityAssociationForPrefixDelegation { options, .. }
+            | DHCPv6Option::IdentityAssociationPrefix { options, .. } => options,
+            _ => &[],
+        }
+    }
+
+    pub fn addresses(&self) -> impl Iterator<Item = &DHCPv6Option<'a>> {
+        self.options()
+            .iter()
+            .filter(|option| matches!(option, DHCPv6Option::IdentityAssociationAddress { .. }))
+    }
+
+    pub fn prefixes(&self) -> impl Iterator<Item = &DHCPv6Option<'a>> {
+        self.options()
+            .iter()
+            .filter(|option| matches!(option, DHCPv6Option::IdentityAssociationPrefix { .. }))
+    }
+
+    pub fn ia_id(&self) -> Option<u32> {
+        match self {
+            DHCPv6Option::IdentityAssociationForNonTemporaryAddresses { id, .. }
+            | DHCPv6Option::IdentityAssociationForTemporaryAddresses { id, .. }
+            | DHCPv6Option::IdentityAssociationForPrefixDelegation { id, .. } => Some(*id),
+            _ => None,
+        }
+    }
+
+    pub fn status(&self) -> Option<(StatusCode, Cow<'_, str>)> {
+        self.options().iter().find_map(|option| match option {
+            DHCPv6Option::StatusCode { code, message } => {
+                Some((*code, String::from_utf8_lossy(message)))
+            }
+            _ => None,
+        })
+    }
+
+    pub fn into_owned(self) -> DHCPv6Option<'static> {
+        match self {
+            DHCPv6Option::CliendID { duid } => DHCPv6Option::CliendID {
+                duid: duid.into_owned(),
+            },
+            DHCPv6Option::ServerID { duid } => DHCPv6Option::ServerID {
+                duid: duid.into_owned(),
+            },
+            DHCPv6Option::IdentityAssociationForNonTemporaryAddresses {
+                id,
+                time_1,
+                time_2,
+                options,
+            } => DHCPv6Option::IdentityAssociationForNonTemporaryAddresses {
+                id,
+                time_1,
+                time_2,
+                options: options_into_owned(options),
+            },
+            DHCPv6Option::IdentityAssociationForTemporaryAddresses { id, options } => {
+                DHCPv6Option::IdentityAssociationForTemporaryAddresses {
+                    id,
+                    options: options_into_owned(options),
+                }
+            }
+            DHCPv6Option::IdentityAssociationAddress {
+                address,
+                prefered_lifetime,
+                valid_lifetime,
+                options,
+            } => DHCPv6Option::IdentityAssociationAddress {
+                address,
+                prefered_lifetime,
+                valid_lifetime,
+                options: options_into_owned(options),
+            },
+            DHCPv6Option::OptionRequest { options } => DHCPv6Option::OptionRequest { options },
+            DHCPv6Option::Preference { pref_value } => DHCPv6Option::Preference { pref_value },
+            DHCPv6Option::ElapstedTime { elapsed_time } => {
+                DHCPv6Option::ElapstedTime { elapsed_time }
+            }
+            DHCPv6Option::RelayMessage { data } => DHCPv6Option::RelayMessage {
+                data: Cow::Owned(data.into_owned()),
+            },
+            DHCPv6Option::Authentication {
+                protocol,
+                algorithm,
+                rdm,
+                replay_detection,
+                authentication_information,
+            } => DHCPv6Option::Authentication {
+                protocol,
+                algorithm,
+                rdm,
+                replay_detection,
+                authentication_information: Cow::Owned(authentication_information.into_owned()),
+            },
+            DHCPv6Option::ServerUnicast { address } => DHCPv6Option::ServerUnicast { address },
+            DHCPv6Option::StatusCode { code, message } => DHCPv6Option::StatusCode {
+                code,
+                message: Cow::Owned(message.into_owned()),
+            },
+            DHCPv6Option::RapidCommit {} => DHCPv6Option::RapidCommit {},
+            DHCPv6Option::UserClass { data } => DHCPv6Option::UserClass {
+                data: Cow::Owned(data.into_owned()),
+            },
+            DHCPv6Option::VendorClass {
+                enterprise_number,
+                data,
+            } => DHCPv6Option::VendorClass {
+                enterprise_number,
+                data: Cow::Owned(data.into_owned()),
+            },
+            DHCPv6Option::VendorSpecificInformation {
+                enterprise_number,
+                data,
+            } => DHCPv6Option::VendorSpecificInformation {
+                enterprise_number,
+                data: Cow::Owned(data.into_owned()),
+            },
+            DHCPv6Option::InterfaceID { data } => DHCPv6Option::InterfaceID {
+                data: Cow::Owned(data.into_owned()),
+            },
+            DHCPv6Option::RemoteID {
+                enterprise_number,
+                data,
+            } => DHCPv6Option::RemoteID {
+                enterprise_number,
+                data: Cow::Owned(data.into_owned()),
+            },
+            DHCPv6Option::SubscriberID { data } => DHCPv6Option::SubscriberID {
+                data: Cow::Owned(data.into_owned()),
+            },
+            DHCPv6Option::ReconfigureMessage { message_type } => {
+                DHCPv6Option::ReconfigureMessage { message_type }
+            }
+            DHCPv6Option::ReconfigureAccept {} => DHCPv6Option::ReconfigureAccept {},
+            DHCPv6Option::IdentityAssociationForPrefixDelegation {
+                id,
+                time_1,
+                time_2,
+                options,
+            } => DHCPv6Option::IdentityAssociationForPrefixDelegation {
+                id,
+                time_1,
+                time_2,
+                options: options_into_owned(options),
+            },
+            DHCPv6Option::IdentityAssociationPrefix {
+                prefered_lifetime,
+                valid_lifetime,
+                prefix_length,
+                prefix,
+                options,
+            } => DHCPv6Option::IdentityAssociationPrefix {
+                prefered_lifetime,
+                valid_lifetime,
+                prefix_length,
+                prefix,
+                options: options_into_owned(options),
+            },
+            DHCPv6Option::DnsServers { servers } => DHCPv6Option::DnsServers { servers },
+            DHCPv6Option::DomainSearchList { domains } => DHCPv6Option::DomainSearchList {
+                domains: domains.into_iter().map(DomainName::into_owned).collect(),
+            },
+            DHCPv6Option::ClientFqdn {
+                server_update,
+                server_override,
+                no_update,
+                mbz_flags,
+                domain_name,
+            } => DHCPv6Option::ClientFqdn {
+                server_update,
+                server_override,
+                no_update,
+                mbz_flags,
+                domain_name: domain_name.into_owned(),
+            },
+            DHCPv6Option::ClientLinkLayerAddress {
+                hardware_type,
+                link_layer_address,
+            } => DHCPv6Option::ClientLinkLayerAddress {
+                hardware_type,
+                link_layer_address: Cow::Owned(link_layer_address.into_owned()),
+            },
+            DHCPv6Option::InformationRefreshTime { refresh_time } => {
+                DHCPv6Option::InformationRefreshTime { refresh_time }
+            }
+            DHCPv6Option::SolMaxRt { sol_max_rt } => DHCPv6Option::SolMaxRt { sol_max_rt },
+            DHCPv6Option::InfMaxRt { inf_max_rt } => DHCPv6Option::InfMaxRt { inf_max_rt },
+            DHCPv6Option::SntpServers { servers } => DHCPv6Option::SntpServers { servers },
+            DHCPv6Option::NtpServer { suboptions } => DHCPv6Option::NtpServer {
+                suboptions: suboptions
+                    .into_iter()
+                    .map(NtpSuboption::into_owned)
+                    .collect(),
+            },
+            DHCPv6Option::Unknown { code, data } => DHCPv6Option::Unknown {
+                code,
+                data: Cow::Owned(data.into_owned()),
+            },
+            DHCPv6Option::Malformed { code, data } => DHCPv6Option::Malformed {
+                code,
+                data: Cow::Owned(data.into_owned()),
+            },
+        }
+    }
+
+    pub fn as_borrowed(&self) -> DHCPv6Option<'_> {
+        match self {
+            DHCPv6Option::CliendID { duid } => DHCPv6Option::CliendID {
+                duid: duid.as_borrowed(),
+            },
+            DHCPv6Option::ServerID { duid } => DHCPv6Option::ServerID {
+                duid: duid.as_borrowed(),
+            },
+            DHCPv6Option::IdentityAssociationForNonTemporaryAddresses {
+                id,
+                time_1,
+                time_2,
+                options,
+            } => DHCPv6Option::IdentityAssociationForNonTemporaryAddresses {
+                id: *id,
+                time_1: *time_1,
+                time_2: *time_2,
+                options: options_as_borrowed(options),
+            },
+            DHCPv6Option::IdentityAssociationForTemporaryAddresses { id, options } => {
+                DHCPv6Option::IdentityAssociationForTemporaryAddresses {
+                    id: *id,
+                    options: options_as_borrowed(options),
+                }
+            }
+            DHCPv6Option::IdentityAssociationAddress {
+                address,
+                prefered_lifetime,
+                valid_lifetime,
+                options,
+            } => DHCPv6Option::IdentityAssociationAddress {
+                address: *address,
+                prefered_lifetime: *prefered_lifetime,
+                valid_lifetime: *valid_lifetime,
+                options: options_as_borrowed(options),
+            },
+            DHCPv6Option::OptionRequest { options } => DHCPv6Option::OptionRequest {
+                options: options.clone(),
+            },
+            DHCPv6Option::Preference { pref_value } => DHCPv6Option::Preference {
+                pref_value: *pref_value,
+            },
+            DHCPv6Option::ElapstedTime { elapsed_time } => DHCPv6Option::ElapstedTime {
+                elapsed_time: *elapsed_time,
+            },
+            DHCPv6Option::RelayMessage { data } => DHCPv6Option::RelayMessage {
+                data: Cow::Borrowed(data),
+            },
+            DHCPv6Option::Authentication {
+                protocol,
+                algorithm,
+                rdm,
+                replay_detection,
+                authentication_information,
+            } => DHCPv6Option::Authentication {
+                protocol: *protocol,
+                algorithm: *algorithm,
+                rdm: *rdm,
+                replay_detection: *replay_detection,
+                authentication_information: Cow::Borrowed(authentication_information),
+            },
+            DHCPv6Option::ServerUnicast { address } => {
+                DHCPv6Option::ServerUnicast { address: *address }
+            }
+            DHCPv6Option::StatusCode { code, message } => DHCPv6Option::StatusCode {
+                code: *code,
+                message: Cow::Borrowed(message),
+            },
+            DHCPv6Option::RapidCommit {} => DHCPv6Option::RapidCommit {},
+            DHCPv6Option::UserClass { data } => DHCPv6Option::UserClass {
+                data: Cow::Borrowed(data),
+            },
+            DHCPv6Option::VendorClass {
+                enterprise_number,
+                data,
+            } => DHCPv6Option::VendorClass {
+                enterprise_number: *enterprise_number,
+                data: Cow::Borrowed(data),
+            },
+            DHCPv6Option::VendorSpecificInformation {
+                enterprise_number,
+                data,
+            } => DHCPv6Option::VendorSpecificInformation {
+                enterprise_number: *enterprise_number,
+                data: Cow::Borrowed(data),
+            },
+            DHCPv6Option::InterfaceID { data } => DHCPv6Option::InterfaceID {
+                data: Cow::Borrowed(data),
+            },
+            DHCPv6Option::RemoteID {
+                enterprise_number,
+                data,
+            } => DHCPv6Option::RemoteID {
+                enterprise_number: *enterprise_number,
+                data: Cow::Borrowed(data),
+            },
+            DHCPv6Option::SubscriberID { data } => DHCPv6Option::SubscriberID {
+                data: Cow::Borrowed(data),
+            },
+            DHCPv6Option::ReconfigureMessage { message_type } => DHCPv6Option::ReconfigureMessage {
+                message_type: *message_type,
+            },
+            DHCPv6Option::ReconfigureAccept {} => DHCPv6Option::ReconfigureAccept {},
+            DHCPv6Option::IdentityAssociationForPrefixDelegation {
+                id,
+                time_1,
+                time_2,
+                options,
+            } => DHCPv6Option::IdentityAssociationForPrefixDelegation {
+                id: *id,
+                time_1: *time_1,
+                time_2: *time_2,
+                options: options_as_borrowed(options),
+            },
+            DHCPv6Option::IdentityAssociationPrefix {
+                prefered_lifetime,
+                valid_lifetime,
+                prefix_length,
+                prefix,
+                options,
+            } => DHCPv6Option::IdentityAssociationPrefix {
+                prefered_lifetime: *prefered_lifetime,
+                valid_lifetime: *valid_lifetime,
+                prefix_length: *prefix_length,
+                prefix: *prefix,
+                options: options_as_borrowed(options),
+            },
+            DHCPv6Option::DnsServers { servers } => DHCPv6Option::DnsServers {
+                servers: servers.clone(),
+            },
+            DHCPv6Option::DomainSearchList { domains } => DHCPv6Option::DomainSearchList {
+                domains: domains.iter().map(DomainName::as_borrowed).collect(),
+            },
+            DHCPv6Option::ClientFqdn {
+                server_update,
+                server_override,
+                no_update,
+                mbz_flags,
+                domain_name,
+            } => DHCPv6Option::ClientFqdn {
+                server_update: *server_update,
+                server_override: *server_override,
+                no_update: *no_update,
+                mbz_flags: *mbz_flags,
+                domain_name: domain_name.as_borrowed(),
+            },
+            DHCPv6Option::ClientLinkLayerAddress {
+                hardware_type,
+                link_layer_address,
+            } => DHCPv6Option::ClientLinkLayerAddress {
+                hardware_type: *hardware_type,
+                link_layer_address: Cow::Borrowed(link_layer_address),
+            },
+            DHCPv6Option::InformationRefreshTime { refresh_time } => {
+                DHCPv6Option::InformationRefreshTime {
+                    refresh_time: *refresh_time,
+                }
+            }
+            DHCPv6Option::SolMaxRt { sol_max_rt } => DHCPv6Option::SolMaxRt {
+                sol_max_rt: *sol_max_rt,
+            },
+            DHCPv6Option::InfMaxRt { inf_max_rt } => DHCPv6Option::InfMaxRt {
+                inf_max_rt: *inf_max_rt,
+            },
+            DHCPv6Option::SntpServers { servers } => DHCPv6Option::SntpServers {
+                servers: servers.clone(),
+            },
+            DHCPv6Option::NtpServer { suboptions } => DHCPv6Option::NtpServer {
+                suboptions: suboptions.iter().map(NtpSuboption::as_borrowed).collect(),
+            },
+            DHCPv6Option::Unknown { code, data } => DHCPv6Option::Unknown {
+                code: *code,
+                data: Cow::Borrowed(data),
+            },
+            DHCPv6Option::Malformed { code, data } => DHCPv6Option::Malformed {
+                code: *code,
+                data: Cow::Borrowed(data),
+            },
+        }
+    }
+}
+
+pub(crate) fn find_interface_id<'o>(options: &'o [DHCPv6Option]) -> Option<&'o [u8]> {
+    options.iter().find_map(|option| match option {
+        DHCPv6Option::InterfaceID { data } => Some(&**data),
+        _ => None,
+    })
+}
+
+pub(crate) fn find_remote_id<'o>(options: &'o [DHCPv6Option]) -> Option<(u32, &'o [u8])> {
+    options.iter().find_map(|option| match option {
+        DHCPv6Option::RemoteID {
+            enterprise_number,
+            data,
+        } => Some((*enterprise_number, &**data)),
+        _ => None,
+    })
+}
+
+pub(crate) fn find_subscriber_id<'o>(options: &'o [DHCPv6Option]) -> Option<&'o [u8]> {
+    options.iter().find_map(|option| match option {
+        DHCPv6Option::SubscriberID { data } => Some(&**data),
+        _ => None,
+    })
+}
+
+pub(crate) fn find_client_link_layer_address<'o>(
+    options: &'o [DHCPv6Option],
+) -> Option<(HardwareType, &'o [u8])> {
+    options.iter().find_map(|option| match option {
+        DHCPv6Option::ClientLinkLayerAddress {
+            hardware_type,
+            link_layer_address,
+        } => Some((*hardware_type, &**link_layer_address)),
+        _ => None,
+    })
+}
+
+pub fn options_into_owned(options: Vec<DHCPv6Option>) -> Vec<DHCPv6Option<'static>> {
+    options.into_iter().map(DHCPv6Option::into_owned).collect()
+}
+
+pub fn options_as_borrowed<'a>(options: &'a [DHCPv6Option]) -> Vec<DHCPv6Option<'a>> {
+    options.iter().map(DHCPv6Option::as_borrowed).collect()
+}
+
+fn parse_dhcpv6_option_client_id(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, duid) = parse_duid(input)?;
 
     Ok((rest, DHCPv6Option::CliendID { duid }))
 }
 
-fn parse_dhcpv6_option_server_id(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, len) = be_u16(input)?;
-    let (rest, duid) = take(len as usize)(rest)?;
+fn parse_dhcpv6_option_server_id(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, duid) = parse_duid(input)?;
 
     Ok((rest, DHCPv6Option::ServerID { duid }))
 }
 
-fn parse_dhcpv6_option_ia_na(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, len) = verify(be_u16, |len: &u16| *len >= 12)(input)?;
-    let (rest, (id, time_1, time_2, options)) =
-        tuple((be_u32, be_u32, be_u32, take(len as usize - 12usize)))(rest)?;
+fn parse_dhcpv6_option_ia_na<'a>(
+    config: &ParseConfig,
+    depth: usize,
+    count: &mut usize,
+    input: &'a [u8],
+) -> ParseResult<'a, DHCPv6Option<'a>> {
+    let (rest, (id, time_1, time_2)) = tuple((be_u32, be_u32, be_u32))(input)?;
+    let (rest, options) = parse_dhcpv6_options_at(config, depth + 1, count, rest)?;
 
     Ok((
         rest,
@@ -110,9 +665,14 @@ fn parse_dhcpv6_option_ia_na(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
     ))
 }
 
-fn parse_dhcpv6_option_ia_ta(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, len) = verify(be_u16, |len: &u16| *len >= 4)(input)?;
-    let (rest, (id, options)) = tuple((be_u32, take(len as usize - 4usize)))(rest)?;
+fn parse_dhcpv6_option_ia_ta<'a>(
+    config: &ParseConfig,
+    depth: usize,
+    count: &mut usize,
+    input: &'a [u8],
+) -> ParseResult<'a, DHCPv6Option<'a>> {
+    let (rest, id) = be_u32(input)?;
+    let (rest, options) = parse_dhcpv6_options_at(config, depth + 1, count, rest)?;
 
     Ok((
         rest,
@@ -120,14 +680,15 @@ fn parse_dhcpv6_option_ia_ta(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
     ))
 }
 
-fn parse_dhcpv6_option_ia(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, len) = verify(be_u16, |len: &u16| *len >= 24)(input)?;
-    let (rest, (address, prefered_lifetime, valid_lifetime, options)) = tuple((
-        parse_ipv6_address,
-        be_u32,
-        be_u32,
-        take(len as usize - 24usize),
-    ))(rest)?;
+fn parse_dhcpv6_option_ia<'a>(
+    config: &ParseConfig,
+    depth: usize,
+    count: &mut usize,
+    input: &'a [u8],
+) -> ParseResult<'a, DHCPv6Option<'a>> {
+    let (rest, (address, prefered_lifetime, valid_lifetime)) =
+        tuple((parse_ipv6_address, be_u32, be_u32))(input)?;
+    let (rest, options) = parse_dhcpv6_options_at(config, depth + 1, count, rest)?;
 
     Ok((
         rest,
@@ -140,40 +701,44 @@ fn parse_dhcpv6_option_ia(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
     ))
 }
 
-fn parse_dhcpv6_option_option_request(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, len) = verify(be_u16, |len: &u16| *len & 1 == 0)(input)?;
-    let count = len as usize / 2;
-    let (rest, options) = many_m_n(count, count, be_u16)(rest)?;
+fn parse_dhcpv6_option_option_request(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let mut options = Vec::with_capacity(input.len() / 2);
+    let mut rest = input;
+    while rest.len() >= 2 {
+        let (next, option) = be_u16(rest)?;
+        options.push(option);
+        rest = next;
+    }
 
     Ok((rest, DHCPv6Option::OptionRequest { options }))
 }
 
-fn parse_dhcpv6_option_preference(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, _len) = verify(be_u16, |len: &u16| *len == 1)(input)?;
-    let pref_value = rest[0];
+fn parse_dhcpv6_option_preference(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, pref_value) = be_u8(input)?;
 
-    Ok((&rest[1..], DHCPv6Option::Preference { pref_value }))
+    Ok((rest, DHCPv6Option::Preference { pref_value }))
 }
 
-fn parse_dhcpv6_option_elapsted_time(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, _len) = verify(be_u16, |len: &u16| *len == 2)(input)?;
-    let (rest, elapsed_time) = be_u16(rest)?;
+fn parse_dhcpv6_option_elapsted_time(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, elapsed_time) = be_u16(input)?;
 
     Ok((rest, DHCPv6Option::ElapstedTime { elapsed_time }))
 }
 
-fn parse_dhcpv6_option_relay_message(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, len) = be_u16(input)?;
-    let (rest, data) = take(len as usize)(rest)?;
+fn parse_dhcpv6_option_relay_message(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, data) = rest(input)?;
 
-    Ok((rest, DHCPv6Option::RelayMessage { data }))
+    Ok((
+        rest,
+        DHCPv6Option::RelayMessage {
+            data: Cow::Borrowed(data),
+        },
+    ))
 }
 
-fn parse_dhcpv6_option_authentication(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, len) = verify(be_u16, |len: &u16| *len >= 11)(input)?;
-    let (protocol, algorithm, rdm) = (rest[0], rest[1], rest[2]);
-    let (rest, (replay_detection, authentication_information)) =
-        tuple((be_u64, take(len as usize - 11)))(&rest[3..])?;
+fn parse_dhcpv6_option_authentication(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, (protocol, algorithm, rdm, replay_detection, authentication_information)) =
+        tuple((be_u8, be_u8, be_u8, be_u64, rest))(input)?;
 
     Ok((
         rest,
@@ -182,152 +747,824 @@ fn parse_dhcpv6_option_authentication(input: &[u8]) -> IResult<&[u8], DHCPv6Opti
             algorithm,
             rdm,
             replay_detection,
-            authentication_information,
+            authentication_information: Cow::Borrowed(authentication_information),
         },
     ))
 }
 
-fn parse_dhcpv6_option_server_unicast(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, _len) = verify(be_u16, |len: &u16| *len == 16)(input)?;
-    let (rest, address) = parse_ipv6_address(rest)?;
+fn parse_dhcpv6_option_server_unicast(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, address) = parse_ipv6_address(input)?;
 
     Ok((rest, DHCPv6Option::ServerUnicast { address }))
 }
 
-fn parse_dhcpv6_option_status_code(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, len) = verify(be_u16, |len: &u16| *len >= 2)(input)?;
-    let (rest, (code, raw_message)) = tuple((be_u16, take(len as usize - 2)))(rest)?;
+fn parse_dhcpv6_option_status_code<'a>(
+    config: &ParseConfig,
+    input: &'a [u8],
+) -> ParseResult<'a, DHCPv6Option<'a>> {
+    let (rest, (code, message)) = tuple((be_u16, rest))(input)?;
 
-    if let Ok(message) = ::std::str::from_utf8(raw_message) {
-        Ok((rest, DHCPv6Option::StatusCode { code, message }))
-    } else {
-        Err(::nom::Err::Error((rest, ::nom::error::ErrorKind::Verify)))
+    if config.status_message_utf8 == Utf8Policy::Strict && core::str::from_utf8(message).is_err() {
+        return fail(message, ErrorKind::InvalidUtf8);
     }
-}
 
-fn parse_dhcpv6_option_rapid_commit(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, _len) = verify(be_u16, |len: &u16| *len == 0)(input)?;
+    Ok((
+        rest,
+        DHCPv6Option::StatusCode {
+            code: StatusCode::from_u16(code),
+            message: Cow::Borrowed(message),
+        },
+    ))
+}
 
-    Ok((rest, DHCPv6Option::RapidCommit {}))
+fn parse_dhcpv6_option_rapid_commit(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    Ok((input, DHCPv6Option::RapidCommit {}))
 }
 
-fn parse_dhcpv6_option_user_class(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, len) = be_u16(input)?;
-    let (rest, data) = take(len as usize)(rest)?;
+fn parse_dhcpv6_option_user_class(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, data) = rest(input)?;
 
-    Ok((rest, DHCPv6Option::UserClass { data }))
+    Ok((
+        rest,
+        DHCPv6Option::UserClass {
+            data: Cow::Borrowed(data),
+        },
+    ))
 }
 
-fn parse_dhcpv6_option_vendor_class(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, len) = verify(be_u16, |len: &u16| *len >= 4)(input)?;
-    let (rest, (enterprise_number, data)) = tuple((be_u32, take(len as usize - 4)))(rest)?;
+fn parse_dhcpv6_option_vendor_class(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, (enterprise_number, data)) = tuple((be_u32, rest))(input)?;
 
     Ok((
         rest,
         DHCPv6Option::VendorClass {
             enterprise_number,
-            data,
+            data: Cow::Borrowed(data),
         },
     ))
 }
 
-fn parse_dhcpv6_option_vendor_specific_information(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, len) = verify(be_u16, |len: &u16| *len >= 4)(input)?;
-    let (rest, (enterprise_number, data)) = tuple((be_u32, take(len as usize - 4)))(rest)?;
+fn parse_dhcpv6_option_vendor_specific_information(
+    input: &[u8],
+) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, (enterprise_number, data)) = tuple((be_u32, rest))(input)?;
 
     Ok((
         rest,
         DHCPv6Option::VendorSpecificInformation {
             enterprise_number,
-            data,
+            data: Cow::Borrowed(data),
+        },
+    ))
+}
+
+fn parse_dhcpv6_option_interface_id(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, data) = rest(input)?;
+
+    Ok((
+        rest,
+        DHCPv6Option::InterfaceID {
+            data: Cow::Borrowed(data),
         },
     ))
 }
 
-fn parse_dhcpv6_option_interface_id(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, len) = be_u16(input)?;
-    let (rest, data) = take(len as usize)(rest)?;
+fn parse_dhcpv6_option_remote_id(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, (enterprise_number, data)) = tuple((be_u32, rest))(input)?;
 
-    Ok((rest, DHCPv6Option::InterfaceID { data }))
+    Ok((
+        rest,
+        DHCPv6Option::RemoteID {
+            enterprise_number,
+            data: Cow::Borrowed(data),
+        },
+    ))
 }
 
-fn parse_dhcpv6_option_reconfigure_message(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, _len) = verify(be_u16, |len: &u16| *len == 1)(input)?;
+fn parse_dhcpv6_option_subscriber_id(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, data) = rest(input)?;
 
     Ok((
-        &rest[1..],
-        DHCPv6Option::ReconfigureMessage {
-            message_type: rest[0],
+        rest,
+        DHCPv6Option::SubscriberID {
+            data: Cow::Borrowed(data),
         },
     ))
 }
 
-fn parse_dhcpv6_option_reconfigure_accept(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, _len) = verify(be_u16, |len: &u16| *len == 0)(input)?;
+fn parse_dhcpv6_option_reconfigure_message(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, message_type) = be_u8(input)?;
+
+    Ok((rest, DHCPv6Option::ReconfigureMessage { message_type }))
+}
 
-    Ok((rest, DHCPv6Option::ReconfigureAccept {}))
+fn parse_dhcpv6_option_reconfigure_accept(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    Ok((input, DHCPv6Option::ReconfigureAccept {}))
 }
 
-pub fn parse_dhcpv6_option(input: &[u8]) -> IResult<&[u8], DHCPv6Option> {
-    let (rest, kind) = be_u16(input)?;
+fn parse_dhcpv6_option_ia_pd<'a>(
+    config: &ParseConfig,
+    depth: usize,
+    count: &mut usize,
+    input: &'a [u8],
+) -> ParseResult<'a, DHCPv6Option<'a>> {
+    let (rest, (id, time_1, time_2)) = tuple((be_u32, be_u32, be_u32))(input)?;
+    let (rest, options) = parse_dhcpv6_options_at(config, depth + 1, count, rest)?;
+
+    Ok((
+        rest,
+        DHCPv6Option::IdentityAssociationForPrefixDelegation {
+            id,
+            time_1,
+            time_2,
+            options,
+        },
+    ))
+}
 
-    match kind {
-        1u16 => parse_dhcpv6_option_client_id(rest),
-        2u16 => parse_dhcpv6_option_server_id(rest),
-        3u16 => parse_dhcpv6_option_ia_na(rest),
-        4u16 => parse_dhcpv6_option_ia_ta(rest),
-        5u16 => parse_dhcpv6_option_ia(rest),
-        6u16 => parse_dhcpv6_option_option_request(rest),
-        7u16 => parse_dhcpv6_option_preference(rest),
-        8u16 => parse_dhcpv6_option_elapsted_time(rest),
-        9u16 => parse_dhcpv6_option_relay_message(rest),
-        // no 10u16
-        11u16 => parse_dhcpv6_option_authentication(rest),
-        12u16 => parse_dhcpv6_option_server_unicast(rest),
-        13u16 => parse_dhcpv6_option_status_code(rest),
-        14u16 => parse_dhcpv6_option_rapid_commit(rest),
-        15u16 => parse_dhcpv6_option_user_class(rest),
-        16u16 => parse_dhcpv6_option_vendor_class(rest),
-        17u16 => parse_dhcpv6_option_vendor_specific_information(rest),
-        18u16 => parse_dhcpv6_option_interface_id(rest),
-        19u16 => parse_dhcpv6_option_reconfigure_message(rest),
-        20u16 => parse_dhcpv6_option_reconfigure_accept(rest),
-        _ => Err(::nom::Err::Error((rest, ::nom::error::ErrorKind::Switch))),
+fn parse_dhcpv6_option_ia_prefix<'a>(
+    config: &ParseConfig,
+    depth: usize,
+    count: &mut usize,
+    input: &'a [u8],
+) -> ParseResult<'a, DHCPv6Option<'a>> {
+    let (rest, (prefered_lifetime, valid_lifetime, prefix_length, prefix)) =
+        tuple((be_u32, be_u32, be_u8, parse_ipv6_address))(input)?;
+
+    if !is_valid_prefix(&prefix, prefix_length) {
+        return fail(input, ErrorKind::InvalidPrefix { prefix_length });
     }
+
+    let (rest, options) = parse_dhcpv6_options_at(config, depth + 1, count, rest)?;
+
+    Ok((
+        rest,
+        DHCPv6Option::IdentityAssociationPrefix {
+            prefered_lifetime,
+            valid_lifetime,
+            prefix_length,
+            prefix,
+            options,
+        },
+    ))
 }
 
-pub fn parse_dhcpv6_options(input: &[u8]) -> IResult<&[u8], Vec<DHCPv6Option>> {
-    let (rest, options) = many0(parse_dhcpv6_option)(input)?;
+fn parse_ipv6_address_list(input: &[u8]) -> ParseResult<'_, Vec<Ipv6Addr>> {
+    let mut addresses = Vec::with_capacity(input.len() / 16);
+    let mut rest = input;
+    while rest.len() >= 16 {
+        let (next, address) = parse_ipv6_address(rest)?;
+        addresses.push(address);
+        rest = next;
+    }
 
-    assert!(rest.len() == 0);
+    Ok((rest, addresses))
+}
+
+fn parse_dhcpv6_option_dns_servers(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, servers) = parse_ipv6_address_list(input)?;
+
+    Ok((rest, DHCPv6Option::DnsServers { servers }))
+}
+
+fn parse_dhcpv6_option_sntp_servers(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, servers) = parse_ipv6_address_list(input)?;
+
+    Ok((rest, DHCPv6Option::SntpServers { servers }))
+}
+
+fn parse_dhcpv6_option_ntp_server(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, suboptions) = parse_ntp_suboptions(input)?;
+
+    Ok((rest, DHCPv6Option::NtpServer { suboptions }))
+}
+
+fn parse_dhcpv6_option_domain_search_list(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, domains) = parse_domain_name_list(input)?;
+
+    Ok((rest, DHCPv6Option::DomainSearchList { domains }))
+}
+
+// RFC 4704 section 4.1.
+const FQDN_FLAG_S: u8 = 0x01;
+const FQDN_FLAG_O: u8 = 0x02;
+const FQDN_FLAG_N: u8 = 0x04;
+const FQDN_FLAGS_MBZ: u8 = !(FQDN_FLAG_S | FQDN_FLAG_O | FQDN_FLAG_N);
+
+fn parse_dhcpv6_option_client_fqdn(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, flags) = be_u8(input)?;
+    let (rest, domain_name) = parse_domain_name(rest)?;
+
+    Ok((
+        rest,
+        DHCPv6Option::ClientFqdn {
+            server_update: flags & FQDN_FLAG_S != 0,
+            server_override: flags & FQDN_FLAG_O != 0,
+            no_update: flags & FQDN_FLAG_N != 0,
+            mbz_flags: flags & FQDN_FLAGS_MBZ,
+            domain_name,
+        },
+    ))
+}
+
+fn parse_dhcpv6_option_client_link_layer_address(
+    input: &[u8],
+) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, (hardware_type, link_layer_address)) = tuple((be_u16, rest))(input)?;
+
+    Ok((
+        rest,
+        DHCPv6Option::ClientLinkLayerAddress {
+            hardware_type: HardwareType::from_u16(hardware_type),
+            link_layer_address: Cow::Borrowed(link_layer_address),
+        },
+    ))
+}
+
+// RFC 8415 section 7.7: 0xffffffff is reserved for infinity, so only
+// timers::INFINITY encodes to it.
+fn seconds(code: u16, duration: Duration) -> Result<u32, EncodeError> {
+    if duration == INFINITY {
+        return Ok(u32::MAX);
+    }
+
+    match u32::try_from(duration.as_secs()) {
+        Ok(value) if value != u32::MAX && duration.subsec_nanos() == 0 => Ok(value),
+        _ => Err(EncodeError::DurationOutOfRange { code, duration }),
+    }
+}
+
+fn parse_seconds(input: &[u8]) -> ParseResult<'_, Duration> {
+    let (rest, value) = be_u32(input)?;
+
+    Ok((rest, Duration::from_secs(u64::from(value))))
+}
+
+fn parse_dhcpv6_option_information_refresh_time(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, refresh_time) = parse_seconds(input)?;
+
+    Ok((rest, DHCPv6Option::InformationRefreshTime { refresh_time }))
+}
+
+fn parse_dhcpv6_option_sol_max_rt(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, sol_max_rt) = parse_seconds(input)?;
+
+    Ok((rest, DHCPv6Option::SolMaxRt { sol_max_rt }))
+}
+
+fn parse_dhcpv6_option_inf_max_rt(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    let (rest, inf_max_rt) = parse_seconds(input)?;
+
+    Ok((rest, DHCPv6Option::InfMaxRt { inf_max_rt }))
+}
+
+pub fn is_valid_prefix(prefix: &Ipv6Addr, prefix_length: u8) -> bool {
+    let host_mask = (!0u128).checked_shr(u32::from(prefix_length)).unwrap_or(0);
+
+    prefix_length <= 128 && u128::from(*prefix) & host_mask == 0
+}
+
+// Options decoded into a typed variant by parse_dhcpv6_option_value. An
+// Unknown option with one of these codes would not parse back as Unknown.
+fn has_typed_variant(code: u16) -> bool {
+    matches!(
+        OptionCode::from_u16(code),
+        OptionCode::ClientId
+            | OptionCode::ServerId
+            | OptionCode::IaNa
+            | OptionCode::IaTa
+            | OptionCode::IaAddr
+            | OptionCode::OptionRequest
+            | OptionCode::Preference
+            | OptionCode::ElapsedTime
+            | OptionCode::RelayMessage
+            | OptionCode::Authentication
+            | OptionCode::ServerUnicast
+            | OptionCode::StatusCode
+            | OptionCode::RapidCommit
+            | OptionCode::UserClass
+            | OptionCode::VendorClass
+            | OptionCode::VendorSpecificInformation
+            | OptionCode::InterfaceId
+            | OptionCode::RemoteId
+            | OptionCode::SubscriberId
+            | OptionCode::ReconfigureMessage
+            | OptionCode::ReconfigureAccept
+            | OptionCode::DnsServers
+            | OptionCode::DomainSearchList
+            | OptionCode::ClientFqdn
+            | OptionCode::ClientLinkLayerAddress
+            | OptionCode::InformationRefreshTime
+            | OptionCode::SolMaxRt
+            | OptionCode::InfMaxRt
+            | OptionCode::SntpServers
+            | OptionCode::NtpServer
+            | OptionCode::IaPd
+            | OptionCode::IaPrefix
+    )
+}
+
+// Options with a single valid value length, the only ones that
+// LengthMismatch::Keep turns into Malformed.
+fn has_fixed_length(code: u16) -> bool {
+    matches!(
+        OptionCode::from_u16(code),
+        OptionCode::Preference
+            | OptionCode::ElapsedTime
+            | OptionCode::ServerUnicast
+            | OptionCode::RapidCommit
+            | OptionCode::ReconfigureMessage
+            | OptionCode::ReconfigureAccept
+            | OptionCode::InformationRefreshTime
+            | OptionCode::SolMaxRt
+            | OptionCode::InfMaxRt
+    )
+}
+
+// A Malformed option whose data is a valid value for its code would parse
+// back as the typed option.
+fn is_valid_option_value(code: u16, data: &[u8]) -> bool {
+    let config = ParseConfig::default();
+
+    has_typed_variant(code)
+        && matches!(
+            parse_dhcpv6_option_value(&config, 0, &mut 0, code, data),
+            Ok((&[], _))
+        )
+}
+
+fn parse_dhcpv6_option_unknown<'a>(
+    config: &ParseConfig,
+    code: u16,
+    input: &'a [u8],
+) -> ParseResult<'a, DHCPv6Option<'a>> {
+    match config.unknown_options {
+        UnknownOptions::Keep => {
+            let (rest, data) = rest(input)?;
+            Ok((
+                rest,
+                DHCPv6Option::Unknown {
+                    code,
+                    data: Cow::Borrowed(data),
+                },
+            ))
+        }
+        UnknownOptions::Reject => fail(input, ErrorKind::UnknownOption(code)),
+    }
+}
+
+fn parse_dhcpv6_option_value<'a>(
+    config: &ParseConfig,
+    depth: usize,
+    count: &mut usize,
+    code: u16,
+    input: &'a [u8],
+) -> ParseResult<'a, DHCPv6Option<'a>> {
+    match OptionCode::from_u16(code) {
+        OptionCode::ClientId => parse_dhcpv6_option_client_id(input),
+        OptionCode::ServerId => parse_dhcpv6_option_server_id(input),
+        OptionCode::IaNa => parse_dhcpv6_option_ia_na(config, depth, count, input),
+        OptionCode::IaTa => parse_dhcpv6_option_ia_ta(config, depth, count, input),
+        OptionCode::IaAddr => parse_dhcpv6_option_ia(config, depth, count, input),
+        OptionCode::OptionRequest => parse_dhcpv6_option_option_request(input),
+        OptionCode::Preference => parse_dhcpv6_option_preference(input),
+        OptionCode::ElapsedTime => parse_dhcpv6_option_elapsted_time(input),
+        OptionCode::RelayMessage => parse_dhcpv6_option_relay_message(input),
+        OptionCode::Authentication => parse_dhcpv6_option_authentication(input),
+        OptionCode::ServerUnicast => parse_dhcpv6_option_server_unicast(input),
+        OptionCode::StatusCode => parse_dhcpv6_option_status_code(config, input),
+        OptionCode::RapidCommit => parse_dhcpv6_option_rapid_commit(input),
+        OptionCode::UserClass => parse_dhcpv6_option_user_class(input),
+        OptionCode::VendorClass => parse_dhcpv6_option_vendor_class(input),
+        OptionCode::VendorSpecificInformation => {
+            parse_dhcpv6_option_vendor_specific_information(input)
+        }
+        OptionCode::InterfaceId => parse_dhcpv6_option_interface_id(input),
+        OptionCode::RemoteId => parse_dhcpv6_option_remote_id(input),
+        OptionCode::SubscriberId => parse_dhcpv6_option_subscriber_id(input),
+        OptionCode::ReconfigureMessage => parse_dhcpv6_option_reconfigure_message(input),
+        OptionCode::ReconfigureAccept => parse_dhcpv6_option_reconfigure_accept(input),
+        OptionCode::DnsServers => parse_dhcpv6_option_dns_servers(input),
+        OptionCode::DomainSearchList => parse_dhcpv6_option_domain_search_list(input),
+        OptionCode::ClientFqdn => parse_dhcpv6_option_client_fqdn(input),
+        OptionCode::ClientLinkLayerAddress => parse_dhcpv6_option_client_link_layer_address(input),
+        OptionCode::InformationRefreshTime => parse_dhcpv6_option_information_refresh_time(input),
+        OptionCode::SolMaxRt => parse_dhcpv6_option_sol_max_rt(input),
+        OptionCode::InfMaxRt => parse_dhcpv6_option_inf_max_rt(input),
+        OptionCode::SntpServers => parse_dhcpv6_option_sntp_servers(input),
+        OptionCode::NtpServer => parse_dhcpv6_option_ntp_server(input),
+        OptionCode::IaPd => parse_dhcpv6_option_ia_pd(config, depth, count, input),
+        OptionCode::IaPrefix => parse_dhcpv6_option_ia_prefix(config, depth, count, input),
+        _ => parse_dhcpv6_option_unknown(config, code, input),
+    }
+}
+
+pub fn parse_dhcpv6_option(input: &[u8]) -> ParseResult<'_, DHCPv6Option<'_>> {
+    parse_dhcpv6_option_with(&ParseConfig::default(), input)
+}
+
+pub fn parse_dhcpv6_option_with<'a>(
+    config: &ParseConfig,
+    input: &'a [u8],
+) -> ParseResult<'a, DHCPv6Option<'a>> {
+    parse_dhcpv6_option_at(config, 0, &mut 0, input)
+}
+
+fn parse_dhcpv6_option_at<'a>(
+    config: &ParseConfig,
+    depth: usize,
+    count: &mut usize,
+    input: &'a [u8],
+) -> ParseResult<'a, DHCPv6Option<'a>> {
+    let (rest, (code, len)) =
+        with_kind(ErrorKind::TruncatedOption, tuple((be_u16, be_u16)))(input)?;
+    let (rest, value) = take::<_, _, ParseError<&[u8]>>(len as usize)(rest).map_err(|_| {
+        let mut error = ParseError::new(input, ErrorKind::TruncatedOption);
+        error.path.push(code);
+        nom::Err::Error(error)
+    })?;
+
+    let keep = config.length_mismatch == LengthMismatch::Keep;
+    let malformed = || {
+        Ok((
+            rest,
+            DHCPv6Option::Malformed {
+                code,
+                data: Cow::Borrowed(value),
+            },
+        ))
+    };
+    let bad_length = || {
+        if keep && has_fixed_length(code) {
+            return malformed();
+        }
+        let mut error = ParseError::new(input, ErrorKind::BadOptionLength { code, length: len });
+        error.path.push(code);
+        Err(nom::Err::Error(error))
+    };
+
+    match parse_dhcpv6_option_value(config, depth, count, code, value) {
+        Ok((&[], option)) => Ok((rest, option)),
+        Ok(_) => bad_length(),
+        Err(nom::Err::Error(ParseError {
+            kind: ErrorKind::Nom(_),
+            ..
+        })) => bad_length(),
+        Err(nom::Err::Error(ParseError {
+            kind: ErrorKind::BadSuboptionLength { .. },
+            ..
+        })) if keep => malformed(),
+        Err(e) => Err(e.map(|mut e| {
+            e.path.insert(0, code);
+            e
+        })),
+    }
+}
+
+pub fn parse_dhcpv6_options(input: &[u8]) -> ParseResult<'_, Vec<DHCPv6Option<'_>>> {
+    parse_dhcpv6_options_with(&ParseConfig::default(), input)
+}
+
+pub fn parse_dhcpv6_options_with<'a>(
+    config: &ParseConfig,
+    input: &'a [u8],
+) -> ParseResult<'a, Vec<DHCPv6Option<'a>>> {
+    parse_dhcpv6_options_at(config, 0, &mut 0, input)
+}
+
+fn parse_dhcpv6_options_at<'a>(
+    config: &ParseConfig,
+    depth: usize,
+    count: &mut usize,
+    input: &'a [u8],
+) -> ParseResult<'a, Vec<DHCPv6Option<'a>>> {
+    let mut options = Vec::new();
+    let mut rest = input;
+
+    if depth > config.max_depth && !input.is_empty() {
+        return fail(input, ErrorKind::NestingTooDeep);
+    }
+
+    while !rest.is_empty() {
+        if rest.len() < 4 {
+            match config.trailing_bytes {
+                TrailingBytes::Ignore => break,
+                TrailingBytes::Reject => return fail(rest, ErrorKind::TrailingBytes),
+            }
+        }
+        if *count == config.max_options {
+            return fail(rest, ErrorKind::TooManyOptions);
+        }
+        *count += 1;
+        let (next, option) = parse_dhcpv6_option_at(config, depth, count, rest)?;
+        options.push(option);
+        rest = next;
+    }
 
     Ok((rest, options))
 }
 
+fn dhcpv6_option_value_len(option: &DHCPv6Option) -> usize {
+    match option {
+        DHCPv6Option::CliendID { duid } | DHCPv6Option::ServerID { duid } => duid_len(duid),
+        DHCPv6Option::IdentityAssociationForNonTemporaryAddresses { options, .. } => {
+            12 + dhcpv6_options_len(options)
+        }
+        DHCPv6Option::IdentityAssociationForTemporaryAddresses { options, .. } => {
+            4 + dhcpv6_options_len(options)
+        }
+        DHCPv6Option::IdentityAssociationAddress { options, .. } => {
+            24 + dhcpv6_options_len(options)
+        }
+        DHCPv6Option::OptionRequest { options } => 2 * options.len(),
+        DHCPv6Option::Preference { .. } => 1,
+        DHCPv6Option::ElapstedTime { .. } => 2,
+        DHCPv6Option::RelayMessage { data }
+        | DHCPv6Option::UserClass { data }
+        | DHCPv6Option::InterfaceID { data }
+        | DHCPv6Option::SubscriberID { data }
+        | DHCPv6Option::Unknown { data, .. }
+        | DHCPv6Option::Malformed { data, .. } => data.len(),
+        DHCPv6Option::Authentication {
+            authentication_information,
+            ..
+        } => 11 + authentication_information.len(),
+        DHCPv6Option::ServerUnicast { .. } => 16,
+        DHCPv6Option::StatusCode { message, .. } => 2 + message.len(),
+        DHCPv6Option::RapidCommit {} | DHCPv6Option::ReconfigureAccept {} => 0,
+        DHCPv6Option::DnsServers { servers } | DHCPv6Option::SntpServers { servers } => {
+            16 * servers.len()
+        }
+        DHCPv6Option::NtpServer { suboptions } => ntp_suboptions_len(suboptions),
+        DHCPv6Option::DomainSearchList { domains } => domain_name_list_len(domains),
+        DHCPv6Option::ClientFqdn { domain_name, .. } => 1 + domain_name_len(domain_name),
+        DHCPv6Option::ClientLinkLayerAddress {
+            link_layer_address, ..
+        } => 2 + link_layer_address.len(),
+        DHCPv6Option::InformationRefreshTime { .. }
+        | DHCPv6Option::SolMaxRt { .. }
+        | DHCPv6Option::InfMaxRt { .. } => 4,
+        DHCPv6Option::IdentityAssociationForPrefixDelegation { options, .. } => {
+            12 + dhcpv6_options_len(options)
+        }
+        DHCPv6Option::IdentityAssociationPrefix { options, .. } => 25 + dhcpv6_options_len(options),
+        DHCPv6Option::VendorClass { data, .. }
+        | DHCPv6Option::VendorSpecificInformation { data, .. }
+        | DHCPv6Option::RemoteID { data, .. } => 4 + data.len(),
+        DHCPv6Option::ReconfigureMessage { .. } => 1,
+    }
+}
+
+pub fn dhcpv6_option_len(option: &DHCPv6Option) -> usize {
+    4 + dhcpv6_option_value_len(option)
+}
+
+pub fn dhcpv6_options_len(options: &[DHCPv6Option]) -> usize {
+    options.iter().map(dhcpv6_option_len).sum()
+}
+
+pub fn encode_dhcpv6_option<S: Sink>(
+    option: &DHCPv6Option,
+    output: &mut S,
+) -> Result<(), EncodeError> {
+    let code = option.code().to_u16();
+    match option {
+        DHCPv6Option::Unknown { .. } if has_typed_variant(code) => {
+            return Err(EncodeError::NonCanonicalOption { code });
+        }
+        DHCPv6Option::Malformed { data, .. } if is_valid_option_value(code, data) => {
+            return Err(EncodeError::NonCanonicalOption { code });
+        }
+        DHCPv6Option::IdentityAssociationPrefix {
+            prefix_length,
+            prefix,
+            ..
+        } if !is_valid_prefix(prefix, *prefix_length) => {
+            return Err(EncodeError::InvalidPrefix {
+                prefix: *prefix,
+                prefix_length: *prefix_length,
+            });
+        }
+        _ => {}
+    }
+    let len = check_length(code, dhcpv6_option_value_len(option))?;
+
+    output.put(&code.to_be_bytes());
+    output.put(&len.to_be_bytes());
+
+    match option {
+        DHCPv6Option::CliendID { duid } | DHCPv6Option::ServerID { duid } => {
+            encode_duid(duid, output)?
+        }
+        DHCPv6Option::IdentityAssociationForNonTemporaryAddresses {
+            id,
+            time_1,
+            time_2,
+            options,
+        } => {
+            output.put(&id.to_be_bytes());
+            output.put(&time_1.to_be_bytes());
+            output.put(&time_2.to_be_bytes());
+            encode_dhcpv6_options(options, output)?;
+        }
+        DHCPv6Option::IdentityAssociationForTemporaryAddresses { id, options } => {
+            output.put(&id.to_be_bytes());
+            encode_dhcpv6_options(options, output)?;
+        }
+        DHCPv6Option::IdentityAssociationAddress {
+            address,
+            prefered_lifetime,
+            valid_lifetime,
+            options,
+        } => {
+            encode_ipv6_address(address, output);
+            output.put(&prefered_lifetime.to_be_bytes());
+            output.put(&valid_lifetime.to_be_bytes());
+            encode_dhcpv6_options(options, output)?;
+        }
+        DHCPv6Option::OptionRequest { options } => {
+            for option in options {
+                output.put(&option.to_be_bytes());
+            }
+        }
+        DHCPv6Option::Preference { pref_value } => output.put(&[*pref_value]),
+        DHCPv6Option::ElapstedTime { elapsed_time } => output.put(&elapsed_time.to_be_bytes()),
+        DHCPv6Option::RelayMessage { data }
+        | DHCPv6Option::UserClass { data }
+        | DHCPv6Option::InterfaceID { data }
+        | DHCPv6Option::SubscriberID { data }
+        | DHCPv6Option::Unknown { data, .. }
+        | DHCPv6Option::Malformed { data, .. } => output.put(data),
+        DHCPv6Option::Authentication {
+            protocol,
+            algorithm,
+            rdm,
+            replay_detection,
+            authentication_information,
+        } => {
+            output.put(&[*protocol, *algorithm, *rdm]);
+            output.put(&replay_detection.to_be_bytes());
+            output.put(authentication_information);
+        }
+        DHCPv6Option::ServerUnicast { address } => encode_ipv6_address(address, output),
+        DHCPv6Option::StatusCode { code, message } => {
+            output.put(&code.to_u16().to_be_bytes());
+            output.put(message);
+        }
+        DHCPv6Option::RapidCommit {} | DHCPv6Option::ReconfigureAccept {} => {}
+        DHCPv6Option::VendorClass {
+            enterprise_number,
+            data,
+        }
+        | DHCPv6Option::VendorSpecificInformation {
+            enterprise_number,
+            data,
+        }
+        | DHCPv6Option::RemoteID {
+            enterprise_number,
+            data,
+        } => {
+            output.put(&enterprise_number.to_be_bytes());
+            output.put(data);
+        }
+        DHCPv6Option::ReconfigureMessage { message_type } => output.put(&[*message_type]),
+        DHCPv6Option::DnsServers { servers } | DHCPv6Option::SntpServers { servers } => {
+            for server in servers {
+                encode_ipv6_address(server, output);
+            }
+        }
+        DHCPv6Option::NtpServer { suboptions } => encode_ntp_suboptions(suboptions, output)?,
+        DHCPv6Option::DomainSearchList { domains } => encode_domain_name_list(domains, output),
+        DHCPv6Option::ClientFqdn {
+            server_update,
+            server_override,
+            no_update,
+            mbz_flags,
+            domain_name,
+        } => {
+            let mut flags = mbz_flags & FQDN_FLAGS_MBZ;
+            if *server_update {
+                flags |= FQDN_FLAG_S;
+            }
+            if *server_override {
+                flags |= FQDN_FLAG_O;
+            }
+            if *no_update {
+                flags |= FQDN_FLAG_N;
+            }
+            output.put(&[flags]);
+            encode_domain_name(domain_name, output);
+        }
+        DHCPv6Option::ClientLinkLayerAddress {
+            hardware_type,
+            link_layer_address,
+        } => {
+            output.put(&hardware_type.to_u16().to_be_bytes());
+            output.put(link_layer_address);
+        }
+        DHCPv6Option::InformationRefreshTime {
+            refresh_time: duration,
+        }
+        | DHCPv6Option::SolMaxRt {
+            sol_max_rt: duration,
+        }
+        | DHCPv6Option::InfMaxRt {
+            inf_max_rt: duration,
+        } => output.put(&seconds(code, *duration)?.to_be_bytes()),
+        DHCPv6Option::IdentityAssociationForPrefixDelegation {
+            id,
+            time_1,
+            time_2,
+            options,
+        } => {
+            output.put(&id.to_be_bytes());
+            output.put(&time_1.to_be_bytes());
+            output.put(&time_2.to_be_bytes());
+            encode_dhcpv6_options(options, output)?;
+        }
+        DHCPv6Option::IdentityAssociationPrefix {
+            prefered_lifetime,
+            valid_lifetime,
+            prefix_length,
+            prefix,
+            options,
+        } => {
+            output.put(&prefered_lifetime.to_be_bytes());
+            output.put(&valid_lifetime.to_be_bytes());
+            output.put(&[*prefix_length]);
+            encode_ipv6_address(prefix, output);
+            encode_dhcpv6_options(options, output)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn encode_dhcpv6_options<S: Sink>(
+    options: &[DHCPv6Option],
+    output: &mut S,
+) -> Result<(), EncodeError> {
+    for option in options {
+        encode_dhcpv6_option(option, output)?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use std::borrow::Cow;
+
+    fn assert_parse_error(input: &[u8], kind: ErrorKind) -> ParseError<&[u8]> {
+        match parse_dhcpv6_option(input).unwrap_err() {
+            nom::Err::Error(e) => {
+                assert_eq!(e.kind, kind);
+                e
+            }
+            e => panic!("unexpected error {:?}", e),
+        }
+    }
+
     #[test]
     fn test_valid_option_client_id() {
-        let input = b"\x00\x01\x00\x04toto";
+        let input = b"\x00\x01\x00\x0a\x00\x03\x00\x01\x00\x11\x22\xaa\xbb\xcc";
         assert_eq!(
             parse_dhcpv6_option(&input[..]),
-            Ok((&b""[..], DHCPv6Option::CliendID { duid: &b"toto"[..] }))
+            Ok((
+                &b""[..],
+                DHCPv6Option::CliendID {
+                    duid: Duid::LinkLayer {
+                        hardware_type: 1,
+                        link_layer_address: Cow::Borrowed(&b"\x00\x11\x22\xaa\xbb\xcc"[..])
+                    }
+                }
+            ))
         );
     }
 
     #[test]
     fn test_valid_option_server_id() {
-        let input = b"\x00\x02\x00\x04toto";
+        let input = b"\x00\x02\x00\x0a\x00\x03\x00\x01\x00\x11\x22\xaa\xbb\xcc";
         assert_eq!(
             parse_dhcpv6_option(&input[..]),
-            Ok((&b""[..], DHCPv6Option::ServerID { duid: &b"toto"[..] }))
+            Ok((
+                &b""[..],
+                DHCPv6Option::ServerID {
+                    duid: Duid::LinkLayer {
+                        hardware_type: 1,
+                        link_layer_address: Cow::Borrowed(&b"\x00\x11\x22\xaa\xbb\xcc"[..])
+                    }
+                }
+            ))
         );
     }
 
     #[test]
     fn test_valid_option_ia_na() {
-        let input = b"\x00\x03\x00\x10\x00\x00\x00\x01\x01\x23\x45\x67\x89\xab\xcd\xeftoto";
+        let input = b"\x00\x03\x00\x16\x00\x00\x00\x01\x01\x23\x45\x67\x89\xab\xcd\xef\x00\x0d\x00\x06\x00\x00toto";
         assert_eq!(
             parse_dhcpv6_option(&input[..]),
             Ok((
@@ -336,7 +1573,10 @@ mod tests {
                     id: 1,
                     time_1: 0x01234567,
                     time_2: 0x89abcdef,
-                    options: &b"toto"[..]
+                    options: vec![DHCPv6Option::StatusCode {
+                        code: StatusCode::Success,
+                        message: Cow::Borrowed(&b"toto"[..])
+                    }]
                 }
             ))
         );
@@ -344,14 +1584,17 @@ mod tests {
 
     #[test]
     fn test_valid_option_ia_ta() {
-        let input = b"\x00\x04\x00\x08\x00\x00\x00\x01toto";
+        let input = b"\x00\x04\x00\x0e\x00\x00\x00\x01\x00\x0d\x00\x06\x00\x00toto";
         assert_eq!(
             parse_dhcpv6_option(&input[..]),
             Ok((
                 &b""[..],
                 DHCPv6Option::IdentityAssociationForTemporaryAddresses {
                     id: 1,
-                    options: &b"toto"[..]
+                    options: vec![DHCPv6Option::StatusCode {
+                        code: StatusCode::Success,
+                        message: Cow::Borrowed(&b"toto"[..])
+                    }]
                 }
             ))
         );
@@ -359,10 +1602,10 @@ mod tests {
 
     #[test]
     fn test_valid_option_ia() {
-        let input = b"\x00\x05\x00\x1c\
+        let input = b"\x00\x05\x00\x22\
                     \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x01\
                     \xff\xff\xff\xff\xff\xff\xff\xff\
-                    toto";
+                    \x00\x0d\x00\x06\x00\x00toto";
         assert_eq!(
             parse_dhcpv6_option(&input[..]),
             Ok((
@@ -371,12 +1614,69 @@ mod tests {
                     address: Ipv6Addr::LOCALHOST,
                     prefered_lifetime: 0xffffffff,
                     valid_lifetime: 0xffffffff,
-                    options: &b"toto"[..],
+                    options: vec![DHCPv6Option::StatusCode {
+                        code: StatusCode::Success,
+                        message: Cow::Borrowed(&b"toto"[..])
+                    }],
                 }
             ))
         );
     }
 
+    #[test]
+    fn test_ia_helpers() {
+        let input = b"\x00\x03\x00\x38\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\
+                      \x00\x05\x00\x18\
+                      \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x01\
+                      \x00\x00\x00\x01\x00\x00\x00\x02\
+                      \x00\x0d\x00\x0c\x00\x02no address";
+        let (_, option) = parse_dhcpv6_option(&input[..]).unwrap();
+
+        let addresses: Vec<_> = option.addresses().collect();
+        assert_eq!(
+            addresses,
+            vec![&DHCPv6Option::IdentityAssociationAddress {
+                address: Ipv6Addr::LOCALHOST,
+                prefered_lifetime: 1,
+                valid_lifetime: 2,
+                options: vec![],
+            }]
+        );
+        assert_eq!(
+            option.status(),
+            Some((StatusCode::NoAddrsAvail, "no address".into()))
+        );
+        assert_eq!(addresses[0].status(), None);
+        assert_eq!(option.ia_id(), Some(1));
+        assert_eq!(addresses[0].ia_id(), None);
+        assert!(DHCPv6Option::RapidCommit {}.options().is_empty());
+    }
+
+    #[test]
+    fn test_nested_option_errors() {
+        let input = b"\x00\x04\x00\x0c\x00\x00\x00\x01\x00\x08\x00\x01\x00\x00\x00\x00";
+        match parse_dhcpv6_option(&input[..]).unwrap_err() {
+            nom::Err::Error(e) => {
+                assert_eq!(e.kind, ErrorKind::BadOptionLength { code: 8, length: 1 });
+                assert_eq!(e.path, vec![4, 8]);
+            }
+            e => panic!("unexpected error {:?}", e),
+        }
+
+        let input = b"\x00\x04\x00\x0e\x00\x00\x00\x01\x00\x0d\x00\x06\x00\x00toto";
+        let config = ParseConfig {
+            max_depth: 0,
+            ..ParseConfig::default()
+        };
+        match parse_dhcpv6_option_with(&config, &input[..]).unwrap_err() {
+            nom::Err::Error(e) => {
+                assert_eq!(e.kind, ErrorKind::NestingTooDeep);
+                assert_eq!(e.path, vec![4]);
+            }
+            e => panic!("unexpected error {:?}", e),
+        }
+    }
+
     #[test]
     fn test_valid_option_option_request() {
         let input = b"\x00\x06\x00\x02\x13\x37";
@@ -414,7 +1714,12 @@ mod tests {
         let input = b"\x00\x09\x00\x04toto";
         assert_eq!(
             parse_dhcpv6_option(&input[..]),
-            Ok((&b""[..], DHCPv6Option::RelayMessage { data: &b"toto"[..] }))
+            Ok((
+                &b""[..],
+                DHCPv6Option::RelayMessage {
+                    data: Cow::Borrowed(&b"toto"[..])
+                }
+            ))
         );
     }
 
@@ -430,7 +1735,7 @@ mod tests {
                     algorithm: 1,
                     rdm: 1,
                     replay_detection: 1,
-                    authentication_information: &b"toto"[..]
+                    authentication_information: Cow::Borrowed(&b"toto"[..])
                 }
             ))
         );
@@ -459,13 +1764,43 @@ mod tests {
             Ok((
                 &b""[..],
                 DHCPv6Option::StatusCode {
-                    code: 1,
-                    message: "toto"
+                    code: StatusCode::UnspecFail,
+                    message: Cow::Borrowed(&b"toto"[..])
                 }
             ))
         );
     }
 
+    #[test]
+    fn test_lossy_option_status_code() {
+        let input = b"\x00\x03\x00\x13\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\
+                      \x00\x0d\x00\x03\x00\x02\xe9";
+        match parse_dhcpv6_option(&input[..]).unwrap_err() {
+            nom::Err::Error(e) => {
+                assert_eq!(e.kind, ErrorKind::InvalidUtf8);
+                assert_eq!(e.path, vec![3, 13]);
+            }
+            e => panic!("unexpected error {:?}", e),
+        }
+
+        let config = ParseConfig {
+            status_message_utf8: Utf8Policy::Lossy,
+            ..ParseConfig::default()
+        };
+        let (_, option) = parse_dhcpv6_option_with(&config, &input[..]).unwrap();
+        assert_eq!(
+            option.options(),
+            &[DHCPv6Option::StatusCode {
+                code: StatusCode::NoAddrsAvail,
+                message: Cow::Borrowed(&b"\xe9"[..])
+            }]
+        );
+        assert_eq!(
+            option.status(),
+            Some((StatusCode::NoAddrsAvail, "\u{fffd}".into()))
+        );
+    }
+
     #[test]
     fn test_valid_option_rapid_commit() {
         let input = b"\x00\x0e\x00\x00";
@@ -480,7 +1815,12 @@ mod tests {
         let input = b"\x00\x0f\x00\x04toto";
         assert_eq!(
             parse_dhcpv6_option(&input[..]),
-            Ok((&b""[..], DHCPv6Option::UserClass { data: &b"toto"[..] }))
+            Ok((
+                &b""[..],
+                DHCPv6Option::UserClass {
+                    data: Cow::Borrowed(&b"toto"[..])
+                }
+            ))
         );
     }
 
@@ -493,7 +1833,7 @@ mod tests {
                 &b""[..],
                 DHCPv6Option::VendorClass {
                     enterprise_number: 0xdeadbeef,
-                    data: &b"toto"[..]
+                    data: Cow::Borrowed(&b"toto"[..])
                 }
             ))
         );
@@ -508,7 +1848,7 @@ mod tests {
                 &b""[..],
                 DHCPv6Option::VendorSpecificInformation {
                     enterprise_number: 0xdeadbeef,
-                    data: &b"toto"[..]
+                    data: Cow::Borrowed(&b"toto"[..])
                 }
             ))
         );
@@ -519,7 +1859,286 @@ mod tests {
         let input = b"\x00\x12\x00\x04eth0";
         assert_eq!(
             parse_dhcpv6_option(&input[..]),
-            Ok((&b""[..], DHCPv6Option::InterfaceID { data: &b"eth0"[..] }))
+            Ok((
+                &b""[..],
+                DHCPv6Option::InterfaceID {
+                    data: Cow::Borrowed(&b"eth0"[..])
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_valid_option_remote_id() {
+        let input = b"\x00\x25\x00\x0b\x00\x00\x0d\xe9port-12";
+        assert_eq!(
+            parse_dhcpv6_option(&input[..]),
+            Ok((
+                &b""[..],
+                DHCPv6Option::RemoteID {
+                    enterprise_number: 3561,
+                    data: Cow::Borrowed(&b"port-12"[..])
+                }
+            ))
+        );
+
+        let input = b"\x00\x25\x00\x02\x00\x00";
+        assert_parse_error(
+            &input[..],
+            ErrorKind::BadOptionLength {
+                code: 37,
+                length: 2,
+            },
+        );
+    }
+
+    #[test]
+    fn test_valid_option_subscriber_id() {
+        let input = b"\x00\x26\x00\x06sub-42";
+        assert_eq!(
+            parse_dhcpv6_option(&input[..]),
+            Ok((
+                &b""[..],
+                DHCPv6Option::SubscriberID {
+                    data: Cow::Borrowed(&b"sub-42"[..])
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_valid_option_client_link_layer_address() {
+        let input = b"\x00\x4f\x00\x08\x00\x01\x00\x11\x22\xaa\xbb\xcc";
+        let option = DHCPv6Option::ClientLinkLayerAddress {
+            hardware_type: HardwareType::Ethernet,
+            link_layer_address: Cow::Borrowed(&b"\x00\x11\x22\xaa\xbb\xcc"[..]),
+        };
+        assert_eq!(
+            parse_dhcpv6_option(&input[..]),
+            Ok((&b""[..], option.clone()))
+        );
+
+        let mut output = Vec::new();
+        encode_dhcpv6_option(&option, &mut output).unwrap();
+        assert_eq!(&output[..], &input[..]);
+    }
+
+    #[test]
+    fn test_valid_option_timers() {
+        let input = b"\x00\x20\x00\x04\x00\x00\x00\x3c";
+        assert_eq!(
+            parse_dhcpv6_option(&input[..]),
+            Ok((
+                &b""[..],
+                DHCPv6Option::InformationRefreshTime {
+                    refresh_time: Duration::from_secs(60)
+                }
+            ))
+        );
+
+        let input = b"\x00\x52\x00\x04\x00\x00\x0e\x10";
+        assert_eq!(
+            parse_dhcpv6_option(&input[..]),
+            Ok((
+                &b""[..],
+                DHCPv6Option::SolMaxRt {
+                    sol_max_rt: Duration::from_secs(3600)
+                }
+            ))
+        );
+
+        let input = b"\x00\x53\x00\x04\xff\xff\xff\xff";
+        let (_, option) = parse_dhcpv6_option(&input[..]).unwrap();
+        assert_eq!(
+            option,
+            DHCPv6Option::InfMaxRt {
+                inf_max_rt: INFINITY
+            }
+        );
+        let mut output = Vec::new();
+        encode_dhcpv6_option(&option, &mut output).unwrap();
+        assert_eq!(&output[..], &input[..]);
+        assert_eq!(seconds(83, INFINITY), Ok(0xffff_ffff));
+        assert_eq!(
+            seconds(83, Duration::from_secs(0xffff_fffe)),
+            Ok(0xffff_fffe)
+        );
+        for duration in [Duration::from_secs(1 << 40), Duration::from_millis(1500)].iter() {
+            assert_eq!(
+                encode_dhcpv6_option(
+                    &DHCPv6Option::InfMaxRt {
+                        inf_max_rt: *duration
+                    },
+                    &mut Vec::new()
+                ),
+                Err(EncodeError::DurationOutOfRange {
+                    code: 83,
+                    duration: *duration
+                })
+            );
+        }
+
+        let input = b"\x00\x52\x00\x02\x0e\x10";
+        assert_parse_error(
+            &input[..],
+            ErrorKind::BadOptionLength {
+                code: 82,
+                length: 2,
+            },
+        );
+    }
+
+    #[test]
+    fn test_valid_option_ntp_server() {
+        let input = b"\x00\x38\x00\x25\
+                      \x00\x01\x00\x10\x20\x01\x0d\xb8\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x01\
+                      \x00\x03\x00\x0d\x03ntp\x07example\x00";
+        let (rest, option) = parse_dhcpv6_option(&input[..]).unwrap();
+        assert!(rest.is_empty());
+        match &option {
+            DHCPv6Option::NtpServer { suboptions } => {
+                assert_eq!(suboptions.len(), 2);
+                assert_eq!(
+                    suboptions[0],
+                    NtpSuboption::ServerAddress {
+                        address: "2001:db8::1".parse().unwrap()
+                    }
+                );
+            }
+            option => panic!("unexpected option {:?}", option),
+        }
+
+        let mut output = Vec::new();
+        encode_dhcpv6_option(&option, &mut output).unwrap();
+        assert_eq!(&output[..], &input[..]);
+
+        let input = b"\x00\x38\x00\x08\x00\x02\x00\x04\xff\x02\x01\x01";
+        let kind = ErrorKind::BadSuboptionLength {
+            option: 56,
+            code: 2,
+            length: 4,
+        };
+        let error = assert_parse_error(&input[..], kind);
+        assert_eq!(error.path, vec![56, 2]);
+    }
+
+    #[test]
+    fn test_parse_dhcpv6_option_length_mismatch() {
+        let config = ParseConfig {
+            length_mismatch: LengthMismatch::Keep,
+            ..ParseConfig::default()
+        };
+
+        // Fixed-size options are kept as malformed.
+        let input = b"\x00\x07\x00\x02\x00\xff";
+        assert_eq!(
+            parse_dhcpv6_option_with(&config, &input[..]),
+            Ok((
+                &b""[..],
+                DHCPv6Option::Malformed {
+                    code: 7,
+                    data: Cow::Borrowed(&b"\x00\xff"[..])
+                }
+            ))
+        );
+
+        // Kept options encode back to the exact bytes received.
+        let input = b"\x00\x08\x00\x01\x00";
+        let (_, option) = parse_dhcpv6_option_with(&config, &input[..]).unwrap();
+        assert_eq!(
+            option,
+            DHCPv6Option::Malformed {
+                code: 8,
+                data: Cow::Borrowed(&b"\x00"[..])
+            }
+        );
+        let mut output = Vec::new();
+        encode_dhcpv6_option(&option, &mut output).unwrap();
+        assert_eq!(&output[..], &input[..]);
+        assert_eq!(
+            parse_dhcpv6_option_with(&config, &output[..]),
+            Ok((&b""[..], option))
+        );
+
+        // A valid value is not encoded as malformed.
+        let option = DHCPv6Option::Malformed {
+            code: 7,
+            data: Cow::Borrowed(&b"\x01"[..]),
+        };
+        assert_eq!(
+            encode_dhcpv6_option(&option, &mut Vec::new()),
+            Err(EncodeError::NonCanonicalOption { code: 7 })
+        );
+
+        // So are options with a fixed-size suboption of the wrong length.
+        let input = b"\x00\x38\x00\x08\x00\x02\x00\x04\xff\x02\x01\x01";
+        assert_eq!(
+            parse_dhcpv6_option_with(&config, &input[..]),
+            Ok((
+                &b""[..],
+                DHCPv6Option::Malformed {
+                    code: 56,
+                    data: Cow::Borrowed(&input[4..])
+                }
+            ))
+        );
+
+        // Variable-size options are always rejected.
+        let input = b"\x00\x03\x00\x02\x00\x00";
+        match parse_dhcpv6_option_with(&config, &input[..]).unwrap_err() {
+            nom::Err::Error(e) => {
+                assert_eq!(e.kind, ErrorKind::BadOptionLength { code: 3, length: 2 });
+                assert_eq!(e.path, vec![3]);
+            }
+            e => panic!("unexpected error {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_parse_dhcpv6_options_max_options() {
+        // The limit counts nested options too.
+        let input = b"\x00\x03\x00\x14\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\
+                      \x00\x0e\x00\x00\x00\x0e\x00\x00";
+        let config = ParseConfig {
+            max_options: 3,
+            ..ParseConfig::default()
+        };
+        assert!(parse_dhcpv6_options_with(&config, &input[..]).is_ok());
+
+        let config = ParseConfig {
+            max_options: 2,
+            ..config
+        };
+        match parse_dhcpv6_options_with(&config, &input[..]).unwrap_err() {
+            nom::Err::Error(e) => {
+                assert_eq!(e.kind, ErrorKind::TooManyOptions);
+                assert_eq!(e.path, vec![3]);
+            }
+            e => panic!("unexpected error {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_valid_option_sntp_servers() {
+        let input =
+            b"\x00\x1f\x00\x10\x20\x01\x0d\xb8\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x7b";
+        assert_eq!(
+            parse_dhcpv6_option(&input[..]),
+            Ok((
+                &b""[..],
+                DHCPv6Option::SntpServers {
+                    servers: vec!["2001:db8::7b".parse().unwrap()]
+                }
+            ))
+        );
+
+        let input = b"\x00\x1f\x00\x04\x20\x01\x0d\xb8";
+        assert_parse_error(
+            &input[..],
+            ErrorKind::BadOptionLength {
+                code: 31,
+                length: 4,
+            },
         );
     }
 
@@ -544,6 +2163,374 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_roundtrip_options() {
+        let inputs: &[&[u8]] = &[
+            b"\x00\x01\x00\x04toto",
+            b"\x00\x02\x00\x04toto",
+            b"\x00\x03\x00\x16\x00\x00\x00\x01\x01\x23\x45\x67\x89\xab\xcd\xef\x00\x0d\x00\x06\x00\x00toto",
+            b"\x00\x04\x00\x0e\x00\x00\x00\x01\x00\x0d\x00\x06\x00\x00toto",
+            b"\x00\x05\x00\x22\
+              \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x01\
+              \xff\xff\xff\xff\xff\xff\xff\xff\x00\x0d\x00\x06\x00\x00toto",
+            b"\x00\x06\x00\x04\x13\x37\x00\x17",
+            b"\x00\x07\x00\x01\x01",
+            b"\x00\x08\x00\x02\x00\x01",
+            b"\x00\x09\x00\x04toto",
+            b"\x00\x0b\x00\x0f\x01\x01\x01\x00\x00\x00\x00\x00\x00\x00\x01toto",
+            b"\x00\x0c\x00\x10\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x01",
+            b"\x00\x0d\x00\x06\x00\x01toto",
+            b"\x00\x0e\x00\x00",
+            b"\x00\x0f\x00\x04toto",
+            b"\x00\x10\x00\x08\xde\xad\xbe\xeftoto",
+            b"\x00\x11\x00\x08\xde\xad\xbe\xeftoto",
+            b"\x00\x12\x00\x04eth0",
+            b"\x00\x13\x00\x01\x01",
+            b"\x00\x14\x00\x00",
+            b"\x00\x0a\x00\x00",
+            b"\x13\x37\x00\x04toto",
+            b"\x00\x17\x00\x10\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x01",
+            b"\x00\x18\x00\x05\x03lan\x00",
+        ];
+
+        for input in inputs {
+            let (rest, option) = parse_dhcpv6_option(input).unwrap();
+            assert!(rest.is_empty());
+            assert_eq!(dhcpv6_option_len(&option), input.len());
+            assert_eq!(
+                option.code().to_u16(),
+                u16::from_be_bytes([input[0], input[1]])
+            );
+
+            let mut output = Vec::new();
+            encode_dhcpv6_option(&option, &mut output).unwrap();
+            assert_eq!(&output[..], *input);
+            assert_eq!(parse_dhcpv6_option(&output[..]), Ok((&b""[..], option)));
+        }
+    }
+
+    #[test]
+    fn test_encode_option_too_long() {
+        let data = vec![0u8; 0x10000];
+        let option = DHCPv6Option::UserClass {
+            data: Cow::Borrowed(&data[..]),
+        };
+        let mut output = Vec::new();
+        assert_eq!(
+            encode_dhcpv6_option(&option, &mut output),
+            Err(EncodeError::OptionTooLong {
+                code: 15,
+                length: 0x10000
+            })
+        );
+    }
+
+    #[test]
+    fn test_truncated_options() {
+        let inputs: &[&[u8]] = &[
+            b"\x00\x07\x00\x01",
+            b"\x00\x0b\x00\x0b\x01\x01",
+            b"\x00\x13\x00\x01",
+            b"\x00\x03\x00\x10\x00\x00\x00\x01",
+        ];
+
+        for input in inputs {
+            assert!(parse_dhcpv6_option(input).is_err());
+        }
+    }
+
+    #[test]
+    fn test_trailing_bytes_in_options() {
+        let input = b"\x00\x01\x00\x04toto\x00";
+        assert!(parse_dhcpv6_options(&input[..]).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_dhcpv6_option_never_panics(input in vec(any::<u8>(), 0..512)) {
+            let _ = parse_dhcpv6_option(&input[..]);
+        }
+
+        #[test]
+        fn test_parse_dhcpv6_options_never_panics(input in vec(any::<u8>(), 0..512)) {
+            let _ = parse_dhcpv6_options(&input[..]);
+        }
+
+        #[test]
+        fn test_parse_dhcpv6_option_with_valid_header_never_panics(
+            code in 0u16..32,
+            value in vec(any::<u8>(), 0..64),
+            slack in 0usize..4,
+        ) {
+            let mut input = Vec::new();
+            input.extend_from_slice(&code.to_be_bytes());
+            input.extend_from_slice(&(value.len() as u16).to_be_bytes());
+            input.extend_from_slice(&value[..value.len().saturating_sub(slack)]);
+            if let Ok((_, option)) = parse_dhcpv6_option(&input[..]) {
+                let mut output = Vec::new();
+                encode_dhcpv6_option(&option, &mut output).unwrap();
+                prop_assert_eq!(output, input);
+            }
+        }
+    }
+
+    #[test]
+    fn test_valid_option_dns_servers() {
+        let input = b"\x00\x17\x00\x20\
+                      \x20\x01\x48\x60\x48\x60\x00\x00\x00\x00\x00\x00\x00\x00\x88\x88\
+                      \x20\x01\x48\x60\x48\x60\x00\x00\x00\x00\x00\x00\x00\x00\x88\x44";
+        assert_eq!(
+            parse_dhcpv6_option(&input[..]),
+            Ok((
+                &b""[..],
+                DHCPv6Option::DnsServers {
+                    servers: vec![
+                        "2001:4860:4860::8888".parse().unwrap(),
+                        "2001:4860:4860::8844".parse().unwrap(),
+                    ]
+                }
+            ))
+        );
+
+        let input = b"\x00\x17\x00\x04\x20\x01\x48\x60";
+        assert!(parse_dhcpv6_option(&input[..]).is_err());
+    }
+
+    #[test]
+    fn test_valid_option_domain_search_list() {
+        let input = b"\x00\x18\x00\x12\x07example\x03com\x00\x03lan\x00";
+        let (rest, option) = parse_dhcpv6_option(&input[..]).unwrap();
+        assert!(rest.is_empty());
+        match &option {
+            DHCPv6Option::DomainSearchList { domains } => assert_eq!(
+                domains.iter().map(|d| d.to_string()).collect::<Vec<_>>(),
+                vec!["example.com.", "lan."]
+            ),
+            option => panic!("unexpected option {:?}", option),
+        }
+
+        let input = b"\x00\x18\x00\x04\x03lan";
+        match parse_dhcpv6_option(&input[..]).unwrap_err() {
+            nom::Err::Error(e) => {
+                assert_eq!(e.kind, ErrorKind::InvalidDomainName);
+                assert_eq!(e.path, vec![24]);
+            }
+            e => panic!("unexpected error {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_valid_option_client_fqdn() {
+        let input = b"\x00\x27\x00\x13\x01\x04host\x07example\x03com\x00";
+        let (rest, option) = parse_dhcpv6_option(&input[..]).unwrap();
+        assert!(rest.is_empty());
+        match &option {
+            DHCPv6Option::ClientFqdn {
+                server_update,
+                server_override,
+                no_update,
+                mbz_flags,
+                domain_name,
+            } => {
+                assert_eq!(
+                    (*server_update, *server_override, *no_update),
+                    (true, false, false)
+                );
+                assert_eq!(*mbz_flags, 0);
+                assert!(domain_name.is_fully_qualified());
+                assert_eq!(domain_name.to_string(), "host.example.com.");
+            }
+            option => panic!("unexpected option {:?}", option),
+        }
+
+        let mut output = Vec::new();
+        encode_dhcpv6_option(&option, &mut output).unwrap();
+        assert_eq!(&output[..], &input[..]);
+
+        let input = b"\x00\x27\x00\x06\x06\x04host";
+        let (_, option) = parse_dhcpv6_option(&input[..]).unwrap();
+        match &option {
+            DHCPv6Option::ClientFqdn {
+                server_override,
+                no_update,
+                domain_name,
+                ..
+            } => {
+                assert!(*server_override && *no_update);
+                assert!(!domain_name.is_fully_qualified());
+            }
+            option => panic!("unexpected option {:?}", option),
+        }
+
+        let input = b"\x00\x27\x00\x06\x84\x04host";
+        let (_, option) = parse_dhcpv6_option(&input[..]).unwrap();
+        match &option {
+            DHCPv6Option::ClientFqdn {
+                no_update,
+                mbz_flags,
+                ..
+            } => assert_eq!((*no_update, *mbz_flags), (true, 0x80)),
+            option => panic!("unexpected option {:?}", option),
+        }
+        let mut output = Vec::new();
+        encode_dhcpv6_option(&option, &mut output).unwrap();
+        assert_eq!(&output[..], &input[..]);
+
+        let input = b"\x00\x27\x00\x00";
+        assert_parse_error(
+            &input[..],
+            ErrorKind::BadOptionLength {
+                code: 39,
+                length: 0,
+            },
+        );
+    }
+
+    #[test]
+    fn test_encode_client_fqdn() {
+        for (name, expected) in [
+            (
+                "host.example.com.",
+                &b"\x00\x27\x00\x13\x01\x04host\x07example\x03com\x00"[..],
+            ),
+            ("host", &b"\x00\x27\x00\x06\x01\x04host"[..]),
+        ]
+        .iter()
+        {
+            let option = DHCPv6Option::ClientFqdn {
+                server_update: true,
+                server_override: false,
+                no_update: false,
+                mbz_flags: 0,
+                domain_name: name.parse().unwrap(),
+            };
+            let mut output = Vec::new();
+            encode_dhcpv6_option(&option, &mut output).unwrap();
+            assert_eq!(&output[..], *expected);
+            assert_eq!(dhcpv6_option_len(&option), expected.len());
+            assert_eq!(parse_dhcpv6_option(&output[..]), Ok((&b""[..], option)));
+        }
+    }
+
+    #[test]
+    fn test_valid_option_ia_pd() {
+        let input = b"\x00\x19\x00\x29\x00\x00\x00\x01\x00\x00\x0e\x10\x00\x00\x15\x18\
+                      \x00\x1a\x00\x19\x00\x00\x1c\x20\x00\x00\x2a\x30\x38\
+                      \x20\x01\x0d\xb8\x00\x12\x34\x00\x00\x00\x00\x00\x00\x00\x00\x00";
+        let prefix = DHCPv6Option::IdentityAssociationPrefix {
+            prefered_lifetime: 7200,
+            valid_lifetime: 10800,
+            prefix_length: 56,
+            prefix: "2001:db8:12:3400::".parse().unwrap(),
+            options: vec![],
+        };
+        assert_eq!(
+            parse_dhcpv6_option(&input[..]),
+            Ok((
+                &b""[..],
+                DHCPv6Option::IdentityAssociationForPrefixDelegation {
+                    id: 1,
+                    time_1: 3600,
+                    time_2: 5400,
+                    options: vec![prefix.clone()],
+                }
+            ))
+        );
+
+        let (_, option) = parse_dhcpv6_option(&input[..]).unwrap();
+        assert_eq!(option.prefixes().collect::<Vec<_>>(), vec![&prefix]);
+
+        let mut output = Vec::new();
+        encode_dhcpv6_option(&option, &mut output).unwrap();
+        assert_eq!(&output[..], &input[..]);
+    }
+
+    #[test]
+    fn test_invalid_option_ia_prefix() {
+        let input = b"\x00\x1a\x00\x19\x00\x00\x1c\x20\x00\x00\x2a\x30\x30\
+                      \x20\x01\x0d\xb8\x00\x12\x34\x00\x00\x00\x00\x00\x00\x00\x00\x00";
+        match parse_dhcpv6_option(&input[..]).unwrap_err() {
+            nom::Err::Error(e) => {
+                assert_eq!(e.kind, ErrorKind::InvalidPrefix { prefix_length: 48 });
+                assert_eq!(e.path, vec![26]);
+            }
+            e => panic!("unexpected error {:?}", e),
+        }
+
+        let option = DHCPv6Option::IdentityAssociationPrefix {
+            prefered_lifetime: 1,
+            valid_lifetime: 2,
+            prefix_length: 32,
+            prefix: "2001:db8::1".parse().unwrap(),
+            options: vec![],
+        };
+        let mut output = Vec::new();
+        assert_eq!(
+            encode_dhcpv6_option(&option, &mut output),
+            Err(EncodeError::InvalidPrefix {
+                prefix: "2001:db8::1".parse().unwrap(),
+                prefix_length: 32
+            })
+        );
+        assert!(output.is_empty());
+
+        let localhost = Ipv6Addr::LOCALHOST;
+        assert!(is_valid_prefix(&localhost, 128));
+        assert!(!is_valid_prefix(&localhost, 127));
+        assert!(!is_valid_prefix(&Ipv6Addr::UNSPECIFIED, 129));
+        assert!(is_valid_prefix(&Ipv6Addr::UNSPECIFIED, 0));
+    }
+
+    #[test]
+    fn test_unknown_option() {
+        let input = b"\x13\x37\x00\x04toto";
+        assert_eq!(
+            parse_dhcpv6_option(&input[..]),
+            Ok((
+                &b""[..],
+                DHCPv6Option::Unknown {
+                    code: 0x1337,
+                    data: Cow::Borrowed(&b"toto"[..])
+                }
+            ))
+        );
+
+        let error = parse_dhcpv6_option_with(&ParseConfig::strict(), &input[..]).unwrap_err();
+        match error {
+            nom::Err::Error(e) => {
+                assert_eq!(e.kind, ErrorKind::UnknownOption(0x1337));
+                assert_eq!(e.path, vec![0x1337]);
+            }
+            e => panic!("unexpected error {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_encode_non_canonical_unknown_option() {
+        let option = DHCPv6Option::Unknown {
+            code: 1,
+            data: Cow::Borrowed(&b""[..]),
+        };
+        let mut output = Vec::new();
+        assert_eq!(
+            encode_dhcpv6_option(&option, &mut output),
+            Err(EncodeError::NonCanonicalOption { code: 1 })
+        );
+        assert!(output.is_empty());
+
+        for code in 0..=u16::MAX {
+            let input = [(code >> 8) as u8, code as u8, 0, 0];
+            let unknown = matches!(
+                parse_dhcpv6_option_with(&ParseConfig::strict(), &input[..]),
+                Err(nom::Err::Error(ParseError {
+                    kind: ErrorKind::UnknownOption(_),
+                    ..
+                }))
+            );
+            assert_eq!(has_typed_variant(code), !unknown, "option {}", code);
+        }
+    }
+
     #[test]
     fn test_invalid_option_value() {
         let input = b"\x13\x37\x00\x12";
@@ -0,0 +1,243 @@
+use nom::bytes::complete::take;
+
+#[cfg(feature = "alloc")]
+use crate::config::ParseConfig;
+use crate::error::{with_kind, ErrorKind, ParseError, ParseResult};
+use crate::structs::message_types::{parse_dhcpv6_message_type, DHCPv6MessageType};
+use crate::structs::option_codes::OptionCode;
+#[cfg(feature = "alloc")]
+use crate::structs::options::{parse_dhcpv6_option_with, DHCPv6Option};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct RawOption<'a> {
+    pub code: u16,
+    pub value: &'a [u8],
+    raw: &'a [u8],
+}
+
+impl<'a> RawOption<'a> {
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.raw
+    }
+
+    pub fn option_code(&self) -> OptionCode {
+        OptionCode::from_u16(self.code)
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn parse(&self) -> Result<DHCPv6Option<'a>, ParseError<&'a [u8]>> {
+        self.parse_with(&ParseConfig::default())
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn parse_with(
+        &self,
+        config: &ParseConfig,
+    ) -> Result<DHCPv6Option<'a>, ParseError<&'a [u8]>> {
+        match parse_dhcpv6_option_with(config, self.raw) {
+            Ok((_, option)) => Ok(option),
+            Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => Err(e),
+            Err(nom::Err::Incomplete(_)) => {
+                Err(ParseError::new(self.raw, ErrorKind::TruncatedOption))
+            }
+        }
+    }
+
+    pub fn requested_options(&self) -> impl Iterator<Item = u16> + 'a {
+        let value = if self.code == 6 { self.value } else { &[] };
+
+        value
+            .chunks_exact(2)
+            .map(|code| u16::from_be_bytes([code[0], code[1]]))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OptionsIter<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> OptionsIter<'a> {
+    pub fn new(input: &'a [u8]) -> Self {
+        Self { input }
+    }
+
+    // Errors are returned rather than skipped, so that a truncated message is
+    // not mistaken for one without the option.
+    pub fn find(&self, code: u16) -> Result<Option<RawOption<'a>>, ParseError<&'a [u8]>> {
+        self.find_all(code).next().transpose()
+    }
+
+    pub fn find_all(
+        &self,
+        code: u16,
+    ) -> impl Iterator<Item = Result<RawOption<'a>, ParseError<&'a [u8]>>> {
+        self.clone()
+            .filter(move |option| option.as_ref().map_or(true, |option| option.code == code))
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn typed(self) -> impl Iterator<Item = Result<DHCPv6Option<'a>, ParseError<&'a [u8]>>> {
+        self.map(|option| option.and_then(|option| option.parse()))
+    }
+}
+
+impl<'a> Iterator for OptionsIter<'a> {
+    type Item = Result<RawOption<'a>, ParseError<&'a [u8]>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let input = self.input;
+        if input.is_empty() {
+            return None;
+        }
+
+        if input.len() < 4 {
+            self.input = &[];
+            return Some(Err(ParseError::new(input, ErrorKind::TrailingBytes)));
+        }
+
+        let code = u16::from_be_bytes([input[0], input[1]]);
+        let len = u16::from_be_bytes([input[2], input[3]]) as usize;
+        if input.len() < 4 + len {
+            self.input = &[];
+            let error = ParseError::new(input, ErrorKind::TruncatedOption);
+            return Some(Err(error.in_option(code)));
+        }
+
+        let (raw, rest) = input.split_at(4 + len);
+        self.input = rest;
+
+        Some(Ok(RawOption {
+            code,
+            value: &raw[4..],
+            raw,
+        }))
+    }
+}
+
+pub fn parse_dhcpv6_message_options(
+    input: &[u8],
+) -> ParseResult<'_, (DHCPv6MessageType, OptionsIter<'_>)> {
+    let (rest, message_type) = parse_dhcpv6_message_type(input)?;
+    let header_len = match message_type {
+        DHCPv6MessageType::RelayForw | DHCPv6MessageType::RelayRepl => 33u8,
+        _ => 3u8,
+    };
+    let (rest, _) = with_kind(ErrorKind::TruncatedHeader, take(header_len))(rest)?;
+
+    Ok((&rest[rest.len()..], (message_type, OptionsIter::new(rest))))
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::structs::options::parse_dhcpv6_options;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    const INPUT: &[u8] = b"\x00\x01\x00\x0a\x00\x03\x00\x01\x00\x11\x22\xaa\xbb\xcc\
+          \x00\x06\x00\x04\x00\x17\x00\x18\
+          \x00\x08\x00\x02\x00\x00\
+          \x00\x0f\x00\x04toto\
+          \x00\x0f\x00\x04titi";
+
+    #[test]
+    fn test_options_iter() {
+        let codes: Vec<u16> = OptionsIter::new(INPUT)
+            .map(|option| option.unwrap().code)
+            .collect();
+        assert_eq!(codes, vec![1, 6, 8, 15, 15]);
+
+        let oro = OptionsIter::new(INPUT).find(6).unwrap().unwrap();
+        assert_eq!(oro.value, b"\x00\x17\x00\x18");
+        assert_eq!(oro.requested_options().collect::<Vec<_>>(), vec![23, 24]);
+        assert_eq!(oro.as_bytes(), &INPUT[14..22]);
+        assert_eq!(oro.option_code(), OptionCode::OptionRequest);
+
+        let user_classes: Vec<&[u8]> = OptionsIter::new(INPUT)
+            .find_all(15)
+            .map(|option| option.unwrap().value)
+            .collect();
+        assert_eq!(user_classes, vec![&b"toto"[..], &b"titi"[..]]);
+        assert_eq!(OptionsIter::new(INPUT).find(2), Ok(None));
+
+        let typed: Result<Vec<_>, _> = OptionsIter::new(INPUT).typed().collect();
+        assert_eq!(typed.unwrap(), parse_dhcpv6_options(INPUT).unwrap().1);
+    }
+
+    #[test]
+    fn test_options_iter_errors() {
+        let mut iter = OptionsIter::new(b"\x00\x08\x00\x02\x00\x00\x00\x0f\x00\x04to");
+        assert!(iter.next().unwrap().is_ok());
+        assert_eq!(
+            iter.next(),
+            Some(Err(ParseError {
+                input: &b"\x00\x0f\x00\x04to"[..],
+                kind: ErrorKind::TruncatedOption,
+                path: vec![15],
+            }))
+        );
+        assert_eq!(iter.next(), None);
+
+        let mut iter = OptionsIter::new(b"\x00\x0e\x00\x00\x00");
+        assert!(iter.next().unwrap().is_ok());
+        assert_eq!(
+            iter.next().unwrap().unwrap_err().kind,
+            ErrorKind::TrailingBytes
+        );
+        assert_eq!(iter.next(), None);
+
+        let iter = OptionsIter::new(b"\x00\x08\x00\x02\x00\x00\x00\x0f\x00\x04to");
+        assert_eq!(iter.find(15).unwrap_err().kind, ErrorKind::TruncatedOption);
+        assert_eq!(iter.find(2).unwrap_err().kind, ErrorKind::TruncatedOption);
+        assert_eq!(iter.find(8).unwrap().unwrap().value, b"\x00\x00");
+        let results: Vec<_> = iter.find_all(2).collect();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+
+        let option = OptionsIter::new(b"\x00\x07\x00\x02\x00\x00")
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            option.parse().unwrap_err().kind,
+            ErrorKind::BadOptionLength { code: 7, length: 2 }
+        );
+    }
+
+    #[test]
+    fn test_parse_dhcpv6_message_options() {
+        let input = b"\x01\x00\x00\x01\x00\x08\x00\x02\x00\x00\x00\x01\x00\x04toto";
+        let (_, (message_type, options)) = parse_dhcpv6_message_options(input).unwrap();
+        assert_eq!(message_type, DHCPv6MessageType::Solicit);
+        assert_eq!(options.find(1).unwrap().unwrap().value, b"toto");
+
+        let (_, (message_type, mut options)) = parse_dhcpv6_message_options(
+            b"\x0c\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\
+                  \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x0e\x00\x00",
+        )
+        .unwrap();
+        assert_eq!(message_type, DHCPv6MessageType::RelayForw);
+        assert_eq!(options.next().unwrap().unwrap().code, 14);
+
+        assert_eq!(
+            parse_dhcpv6_message_options(b"\x0c\x00\x00\x00").unwrap_err(),
+            nom::Err::Error(ParseError::new(
+                &b"\x00\x00\x00"[..],
+                ErrorKind::TruncatedHeader
+            ))
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_options_iter_matches_parser(input in vec(any::<u8>(), 0..256)) {
+            let eager = parse_dhcpv6_options(&input).map(|(_, options)| options);
+            let lazy: Result<Vec<_>, _> = OptionsIter::new(&input).typed().collect();
+            prop_assert_eq!(eager.is_ok(), lazy.is_ok());
+            if let Ok(options) = eager {
+                prop_assert_eq!(options, lazy.unwrap());
+            }
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::net::Ipv6Addr;
+
+use crate::config::ParseConfig;
+use crate::error::{fail, ErrorKind, ParseResult};
+use crate::structs::header::{parse_dhcpv6_header_with, DHCPv6Header};
+use crate::structs::link_layer::{HardwareType, MacAddr};
+use crate::structs::message_types::DHCPv6MessageType;
+use crate::structs::options::{
+    find_client_link_layer_address, find_interface_id, find_remote_id, find_subscriber_id,
+    options_as_borrowed, options_into_owned, DHCPv6Option,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelayHop<'a> {
+    pub message_type: DHCPv6MessageType,
+    pub hop_count: u8,
+    pub link_address: Ipv6Addr,
+    pub peer_address: Ipv6Addr,
+    pub options: Vec<DHCPv6Option<'a>>,
+}
+
+impl<'a> RelayHop<'a> {
+    pub fn interface_id(&self) -> Option<&[u8]> {
+        find_interface_id(&self.options)
+    }
+
+    pub fn remote_id(&self) -> Option<(u32, &[u8])> {
+        find_remote_id(&self.options)
+    }
+
+    pub fn subscriber_id(&self) -> Option<&[u8]> {
+        find_subscriber_id(&self.options)
+    }
+
+    pub fn client_link_layer_address(&self) -> Option<(HardwareType, &[u8])> {
+        find_client_link_layer_address(&self.options)
+    }
+
+    pub fn client_mac_address(&self) -> Option<MacAddr> {
+        let (hardware_type, address) = self.client_link_layer_address()?;
+
+        MacAddr::from_link_layer(hardware_type, address)
+    }
+
+    pub fn into_owned(self) -> RelayHop<'static> {
+        RelayHop {
+            message_type: self.message_type,
+            hop_count: self.hop_count,
+            link_address: self.link_address,
+            peer_address: self.peer_address,
+            options: options_into_owned(self.options),
+        }
+    }
+
+    pub fn as_borrowed(&self) -> RelayHop<'_> {
+        RelayHop {
+            message_type: self.message_type,
+            hop_count: self.hop_count,
+            link_address: self.link_address,
+            peer_address: self.peer_address,
+            options: options_as_borrowed(&self.options),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelayChain<'a> {
+    pub hops: Vec<RelayHop<'a>>,
+    pub message: DHCPv6Header<'a>,
+}
+
+impl<'a> RelayChain<'a> {
+    pub fn into_owned(self) -> RelayChain<'static> {
+        RelayChain {
+            hops: self.hops.into_iter().map(RelayHop::into_owned).collect(),
+            message: self.message.into_owned(),
+        }
+    }
+
+    pub fn as_borrowed(&self) -> RelayChain<'_> {
+        RelayChain {
+            hops: self.hops.iter().map(RelayHop::as_borrowed).collect(),
+            message: self.message.as_borrowed(),
+        }
+    }
+}
+
+pub fn parse_relay_chain(input: &[u8]) -> ParseResult<'_, RelayChain<'_>> {
+    parse_relay_chain_with(&ParseConfig::default(), input)
+}
+
+pub fn parse_relay_chain_with<'a>(
+    config: &ParseConfig,
+    input: &'a [u8],
+) -> ParseResult<'a, RelayChain<'a>> {
+    let (rest, mut header) = parse_dhcpv6_header_with(config, input)?;
+    let mut current = input;
+    let mut hops = Vec::new();
+
+    while let DHCPv6Header::RelayAgentServer {
+        message_type,
+        hop_count,
+        link_address,
+        peer_address,
+        mut options,
+    } = header
+    {
+        if hops.len() >= config.max_relay_hops {
+            return fail(current, ErrorKind::TooManyRelayHops);
+        }
+
+        let data = match options
+            .iter()
+            .position(|option| matches!(option, DHCPv6Option::RelayMessage { .. }))
+            .map(|index| options.remove(index))
+        {
+            Some(DHCPv6Option::RelayMessage {
+                data: Cow::Borrowed(data),
+            }) => data,
+            _ => return fail(current, ErrorKind::MissingRelayMessage),
+        };
+
+        hops.push(RelayHop {
+            message_type,
+            hop_count,
+            link_address,
+            peer_address,
+            options,
+        });
+
+        let (_, inner) = parse_dhcpv6_header_with(config, data).map_err(|e| {
+            e.map(|mut e| {
+                e.path.insert(0, 9);
+                e
+            })
+        })?;
+        header = inner;
+        current = data;
+    }
+
+    Ok((
+        rest,
+        RelayChain {
+            hops,
+            message: header,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParseError;
+    use crate::structs::duid::Duid;
+
+    fn relay_forward(hop_count: u8, interface_id: &[u8], message: &[u8]) -> Vec<u8> {
+        let mut output = vec![12u8, hop_count];
+        output.extend_from_slice(&[0u8; 16]);
+        output.extend_from_slice(&Ipv6Addr::LOCALHOST.octets());
+        output.extend_from_slice(&[0x00, 0x12]);
+        output.extend_from_slice(&(interface_id.len() as u16).to_be_bytes());
+        output.extend_from_slice(interface_id);
+        output.extend_from_slice(&[0x00, 0x09]);
+        output.extend_from_slice(&(message.len() as u16).to_be_bytes());
+        output.extend_from_slice(message);
+        output
+    }
+
+    #[test]
+    fn test_parse_relay_chain() {
+        let solicit = b"\x01\x00\x00\x01\x00\x01\x00\x04toto";
+        let first = relay_forward(0, b"eth0", &solicit[..]);
+        let second = relay_forward(1, b"eth1", &first[..]);
+
+        let (rest, chain) = parse_relay_chain(&second[..]).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(chain.hops.len(), 2);
+        assert_eq!(chain.hops[0].hop_count, 1);
+        assert_eq!(chain.hops[0].interface_id(), Some(&b"eth1"[..]));
+        assert_eq!(chain.hops[1].hop_count, 0);
+        assert_eq!(chain.hops[1].interface_id(), Some(&b"eth0"[..]));
+        assert_eq!(chain.hops[1].peer_address, Ipv6Addr::LOCALHOST);
+        assert_eq!(
+            chain.message,
+            DHCPv6Header::ClientServer {
+                message_type: DHCPv6MessageType::Solicit,
+                transaction_id: 1,
+                options: vec![DHCPv6Option::CliendID {
+                    duid: Duid::Other {
+                        duid_type: 0x746f,
+                        data: Cow::Borrowed(&b"to"[..])
+                    }
+                }],
+            }
+        );
+
+        let (_, chain) = parse_relay_chain(&solicit[..]).unwrap();
+        assert!(chain.hops.is_empty());
+    }
+
+    #[test]
+    fn test_parse_relay_chain_errors() {
+        let solicit = b"\x01\x00\x00\x01\x00\x01\x00\x04toto";
+        let first = relay_forward(0, b"eth0", &solicit[..]);
+        let second = relay_forward(1, b"eth1", &first[..]);
+
+        let config = ParseConfig {
+            max_relay_hops: 1,
+            ..ParseConfig::default()
+        };
+        match parse_relay_chain_with(&config, &second[..]).unwrap_err() {
+            nom::Err::Error(e) => assert_eq!(e.kind, ErrorKind::TooManyRelayHops),
+            e => panic!("unexpected error {:?}", e),
+        }
+
+        let mut relay = vec![12u8, 0];
+        relay.extend_from_slice(&[0u8; 32]);
+        assert_eq!(
+            parse_relay_chain(&relay[..]).unwrap_err(),
+            nom::Err::Error(ParseError::new(&relay[..], ErrorKind::MissingRelayMessage))
+        );
+
+        let broken = relay_forward(0, b"eth0", &solicit[..solicit.len() - 1]);
+        match parse_relay_chain(&broken[..]).unwrap_err() {
+            nom::Err::Error(e) => {
+                assert_eq!(e.kind, ErrorKind::TruncatedOption);
+                assert_eq!(e.path, vec![9, 1]);
+            }
+            e => panic!("unexpected error {:?}", e),
+        }
+    }
+}
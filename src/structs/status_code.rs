@@ -0,0 +1,149 @@
+use core::fmt;
+
+#[derive(Debug, Clone, Copy)]
+pub enum StatusCode {
+    Success,
+    UnspecFail,
+    NoAddrsAvail,
+    NoBinding,
+    NotOnLink,
+    UseMulticast,
+    NoPrefixAvail,
+    UnknownQueryType,
+    MalformedQuery,
+    NotConfigured,
+    NotAllowed,
+    QueryTerminated,
+    DataMissing,
+    CatchUpComplete,
+    NotSupported,
+    TLSConnectionRefused,
+    AddressInUse,
+    ConfigurationConflict,
+    MissingBindingInformation,
+    OutdatedBindingInformation,
+    ServerShuttingDown,
+    DNSUpdateNotSupported,
+    ExcessiveTimeSkew,
+    Other(u16),
+}
+
+impl StatusCode {
+    pub fn from_u16(value: u16) -> Self {
+        match value {
+            0 => StatusCode::Success,
+            1 => StatusCode::UnspecFail,
+            2 => StatusCode::NoAddrsAvail,
+            3 => StatusCode::NoBinding,
+            4 => StatusCode::NotOnLink,
+            5 => StatusCode::UseMulticast,
+            6 => StatusCode::NoPrefixAvail,
+            7 => StatusCode::UnknownQueryType,
+            8 => StatusCode::MalformedQuery,
+            9 => StatusCode::NotConfigured,
+            10 => StatusCode::NotAllowed,
+            11 => StatusCode::QueryTerminated,
+            12 => StatusCode::DataMissing,
+            13 => StatusCode::CatchUpComplete,
+            14 => StatusCode::NotSupported,
+            15 => StatusCode::TLSConnectionRefused,
+            16 => StatusCode::AddressInUse,
+            17 => StatusCode::ConfigurationConflict,
+            18 => StatusCode::MissingBindingInformation,
+            19 => StatusCode::OutdatedBindingInformation,
+            20 => StatusCode::ServerShuttingDown,
+            21 => StatusCode::DNSUpdateNotSupported,
+            22 => StatusCode::ExcessiveTimeSkew,
+            _ => StatusCode::Other(value),
+        }
+    }
+
+    pub fn to_u16(self) -> u16 {
+        match self {
+            StatusCode::Success => 0,
+            StatusCode::UnspecFail => 1,
+            StatusCode::NoAddrsAvail => 2,
+            StatusCode::NoBinding => 3,
+            StatusCode::NotOnLink => 4,
+            StatusCode::UseMulticast => 5,
+            StatusCode::NoPrefixAvail => 6,
+            StatusCode::UnknownQueryType => 7,
+            StatusCode::MalformedQuery => 8,
+            StatusCode::NotConfigured => 9,
+            StatusCode::NotAllowed => 10,
+            StatusCode::QueryTerminated => 11,
+            StatusCode::DataMissing => 12,
+            StatusCode::CatchUpComplete => 13,
+            StatusCode::NotSupported => 14,
+            StatusCode::TLSConnectionRefused => 15,
+            StatusCode::AddressInUse => 16,
+            StatusCode::ConfigurationConflict => 17,
+            StatusCode::MissingBindingInformation => 18,
+            StatusCode::OutdatedBindingInformation => 19,
+            StatusCode::ServerShuttingDown => 20,
+            StatusCode::DNSUpdateNotSupported => 21,
+            StatusCode::ExcessiveTimeSkew => 22,
+            StatusCode::Other(value) => value,
+        }
+    }
+
+    pub fn is_success(self) -> bool {
+        self == StatusCode::Success
+    }
+}
+
+impl_eq_by_value!(StatusCode, to_u16);
+
+impl fmt::Display for StatusCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatusCode::Success => f.write_str("Success"),
+            StatusCode::UnspecFail => f.write_str("UnspecFail"),
+            StatusCode::NoAddrsAvail => f.write_str("NoAddrsAvail"),
+            StatusCode::NoBinding => f.write_str("NoBinding"),
+            StatusCode::NotOnLink => f.write_str("NotOnLink"),
+            StatusCode::UseMulticast => f.write_str("UseMulticast"),
+            StatusCode::NoPrefixAvail => f.write_str("NoPrefixAvail"),
+            StatusCode::UnknownQueryType => f.write_str("UnknownQueryType"),
+            StatusCode::MalformedQuery => f.write_str("MalformedQuery"),
+            StatusCode::NotConfigured => f.write_str("NotConfigured"),
+            StatusCode::NotAllowed => f.write_str("NotAllowed"),
+            StatusCode::QueryTerminated => f.write_str("QueryTerminated"),
+            StatusCode::DataMissing => f.write_str("DataMissing"),
+            StatusCode::CatchUpComplete => f.write_str("CatchUpComplete"),
+            StatusCode::NotSupported => f.write_str("NotSupported"),
+            StatusCode::TLSConnectionRefused => f.write_str("TLSConnectionRefused"),
+            StatusCode::AddressInUse => f.write_str("AddressInUse"),
+            StatusCode::ConfigurationConflict => f.write_str("ConfigurationConflict"),
+            StatusCode::MissingBindingInformation => f.write_str("MissingBindingInformation"),
+            StatusCode::OutdatedBindingInformation => f.write_str("OutdatedBindingInformation"),
+            StatusCode::ServerShuttingDown => f.write_str("ServerShuttingDown"),
+            StatusCode::DNSUpdateNotSupported => f.write_str("DNSUpdateNotSupported"),
+            StatusCode::ExcessiveTimeSkew => f.write_str("ExcessiveTimeSkew"),
+            StatusCode::Other(value) => match StatusCode::from_u16(*value) {
+                StatusCode::Other(value) => write!(f, "status {}", value),
+                code => code.fmt(f),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_code_roundtrip() {
+        for value in 0..=u16::MAX {
+            assert_eq!(StatusCode::from_u16(value).to_u16(), value);
+        }
+        assert_eq!(StatusCode::from_u16(6), StatusCode::NoPrefixAvail);
+        assert_eq!(StatusCode::from_u16(22), StatusCode::ExcessiveTimeSkew);
+        assert_eq!(StatusCode::from_u16(23), StatusCode::Other(23));
+        assert_eq!(StatusCode::NoBinding.to_string(), "NoBinding");
+        assert_eq!(StatusCode::Other(1000).to_string(), "status 1000");
+        assert_eq!(StatusCode::Other(0), StatusCode::Success);
+        assert!(StatusCode::Other(0).is_success());
+        assert_eq!(StatusCode::Other(2).to_string(), "NoAddrsAvail");
+    }
+}
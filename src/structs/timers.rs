@@ -0,0 +1,52 @@
+use core::time::Duration;
+
+// RFC 8415 section 7.6.
+pub const INFINITY: Duration = Duration::from_secs(0xffff_ffff);
+pub const IRT_DEFAULT: Duration = Duration::from_secs(86400);
+pub const IRT_MINIMUM: Duration = Duration::from_secs(600);
+pub const SOL_MAX_RT: Duration = Duration::from_secs(3600);
+pub const INF_MAX_RT: Duration = Duration::from_secs(3600);
+
+// RFC 8415 sections 21.24 and 21.25.
+pub const MAX_RT_MINIMUM: Duration = Duration::from_secs(60);
+pub const MAX_RT_MAXIMUM: Duration = Duration::from_secs(86400);
+
+pub fn is_infinity(duration: Duration) -> bool {
+    duration == INFINITY
+}
+
+// RFC 8415 section 21.23: a refresh time below IRT_MINIMUM is raised to it.
+pub fn clamp_information_refresh_time(refresh_time: Duration) -> Duration {
+    refresh_time.max(IRT_MINIMUM)
+}
+
+// RFC 8415 sections 21.24 and 21.25: values outside of the valid range must
+// be ignored, in which case the client keeps its current value.
+pub fn check_max_rt(max_rt: Duration) -> Option<Duration> {
+    if (MAX_RT_MINIMUM..=MAX_RT_MAXIMUM).contains(&max_rt) {
+        Some(max_rt)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timers() {
+        assert_eq!(
+            clamp_information_refresh_time(Duration::from_secs(10)),
+            IRT_MINIMUM
+        );
+        assert_eq!(clamp_information_refresh_time(IRT_DEFAULT), IRT_DEFAULT);
+        assert!(is_infinity(clamp_information_refresh_time(INFINITY)));
+
+        assert_eq!(check_max_rt(Duration::from_secs(59)), None);
+        assert_eq!(check_max_rt(MAX_RT_MINIMUM), Some(MAX_RT_MINIMUM));
+        assert_eq!(check_max_rt(SOL_MAX_RT), Some(SOL_MAX_RT));
+        assert_eq!(check_max_rt(Duration::from_secs(86401)), None);
+        assert_eq!(check_max_rt(INFINITY), None);
+    }
+}
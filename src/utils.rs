@@ -1,8 +1,14 @@
+use core::net::Ipv6Addr;
 use nom::combinator::map;
 use nom::number::complete::be_u128;
-use nom::IResult;
-use std::net::Ipv6Addr;
 
-pub(crate) fn parse_ipv6_address(input: &[u8]) -> IResult<&[u8], Ipv6Addr> {
+use crate::encode::Sink;
+use crate::error::ParseResult;
+
+pub(crate) fn parse_ipv6_address(input: &[u8]) -> ParseResult<'_, Ipv6Addr> {
     map(be_u128, Ipv6Addr::from)(input)
 }
+
+pub(crate) fn encode_ipv6_address<S: Sink>(address: &Ipv6Addr, output: &mut S) {
+    output.put(&address.octets());
+}
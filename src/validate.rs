@@ -0,0 +1,498 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::structs::header::{parse_dhcpv6_header, DHCPv6Header};
+use crate::structs::message_types::DHCPv6MessageType;
+use crate::structs::options::DHCPv6Option;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => f.write_str("warning"),
+            Self::Error => f.write_str("error"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ViolationKind {
+    MissingOption { code: u16 },
+    UnexpectedOption { code: u16 },
+    DuplicateOption { code: u16 },
+    MalformedOption { code: u16 },
+    MalformedRelayMessage,
+    ConflictingFqdnFlags,
+    NonZeroMbzFlags { code: u16 },
+}
+
+impl fmt::Display for ViolationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingOption { code } => write!(f, "required option {} missing", code),
+            Self::UnexpectedOption { code } => write!(f, "option {} not allowed here", code),
+            Self::DuplicateOption { code } => {
+                write!(f, "option {} appears more than once", code)
+            }
+            Self::MalformedOption { code } => write!(f, "option {} has a bad length", code),
+            Self::MalformedRelayMessage => f.write_str("relayed message does not parse"),
+            Self::ConflictingFqdnFlags => f.write_str("N and S flags are both set"),
+            Self::NonZeroMbzFlags { code } => {
+                write!(f, "option {} has must-be-zero flags set", code)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Violation {
+    pub severity: Severity,
+    pub kind: ViolationKind,
+    pub path: Vec<u16>,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.kind)?;
+        if let Some((first, others)) = self.path.split_first() {
+            write!(f, " (in option {}", first)?;
+            for code in others {
+                write!(f, " > {}", code)?;
+            }
+            f.write_str(")")?;
+        }
+
+        Ok(())
+    }
+}
+
+// Options covered by RFC 8415 Appendix B and C. Options outside of this list
+// are defined by other documents and are not checked.
+const CHECKED_OPTIONS: &[u16] = &[
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 25, 26, 32, 82, 83,
+];
+
+// Options that may appear at most once in a given message or option.
+const SINGLETON_OPTIONS: &[u16] = &[1, 2, 6, 7, 8, 9, 11, 12, 13, 14, 15, 18, 19, 20, 32, 82, 83];
+
+struct Rule {
+    allowed: &'static [u16],
+    required: &'static [u16],
+}
+
+// RFC 8415 Appendix B.
+fn message_rule(message_type: DHCPv6MessageType) -> Option<Rule> {
+    let (allowed, required): (&[u16], &[u16]) = match message_type {
+        DHCPv6MessageType::Solicit => (&[1, 3, 4, 6, 8, 11, 14, 15, 16, 17, 20, 25], &[1, 8]),
+        DHCPv6MessageType::Advertise => (&[1, 2, 3, 4, 7, 11, 13, 15, 16, 17, 20, 25, 82], &[1, 2]),
+        DHCPv6MessageType::Request => (&[1, 2, 3, 4, 6, 8, 11, 15, 16, 17, 20, 25], &[1, 2, 8]),
+        DHCPv6MessageType::Confirm => (&[1, 3, 4, 6, 8, 11, 15, 16, 17], &[1, 8]),
+        DHCPv6MessageType::Renew => (&[1, 2, 3, 4, 6, 8, 11, 15, 16, 17, 20, 25], &[1, 2, 8]),
+        DHCPv6MessageType::Rebind => (&[1, 3, 4, 6, 8, 11, 15, 16, 17, 20, 25], &[1, 8]),
+        DHCPv6MessageType::Decline => (&[1, 2, 3, 4, 6, 8, 11, 15, 16, 17], &[1, 2, 8]),
+        DHCPv6MessageType::Release => (&[1, 2, 3, 4, 6, 8, 11, 15, 16, 17, 25], &[1, 2, 8]),
+        DHCPv6MessageType::Reply => (
+            &[
+                1, 2, 3, 4, 7, 11, 12, 13, 14, 15, 16, 17, 20, 25, 32, 82, 83,
+            ],
+            &[2],
+        ),
+        DHCPv6MessageType::Reconfigure => (&[1, 2, 6, 11, 17, 19], &[1, 2, 11, 19]),
+        // A client answering a Reconfigure includes the Server Identifier, see
+        // RFC 8415 section 18.2.6, so it is only flagged as a warning below.
+        DHCPv6MessageType::InformationRequest => (&[1, 6, 8, 11, 15, 16, 17, 20], &[8]),
+        DHCPv6MessageType::RelayForw | DHCPv6MessageType::RelayRepl => (&[9, 11, 16, 17, 18], &[9]),
+        _ => return None,
+    };
+
+    Some(Rule { allowed, required })
+}
+
+// RFC 8415 Appendix C.
+fn encapsulated_rule(option: &DHCPv6Option) -> Option<Rule> {
+    let allowed: &[u16] = match option {
+        DHCPv6Option::IdentityAssociationForNonTemporaryAddresses { .. }
+        | DHCPv6Option::IdentityAssociationForTemporaryAddresses { .. } => &[5, 13],
+        DHCPv6Option::IdentityAssociationForPrefixDelegation { .. } => &[13, 26],
+        DHCPv6Option::IdentityAssociationAddress { .. }
+        | DHCPv6Option::IdentityAssociationPrefix { .. } => &[13],
+        _ => return None,
+    };
+
+    Some(Rule {
+        allowed,
+        required: &[],
+    })
+}
+
+struct Validator {
+    violations: Vec<Violation>,
+    path: Vec<u16>,
+}
+
+impl Validator {
+    fn report(&mut self, severity: Severity, kind: ViolationKind) {
+        self.violations.push(Violation {
+            severity,
+            kind,
+            path: self.path.clone(),
+        });
+    }
+
+    fn check_header(&mut self, header: &DHCPv6Header) {
+        let (message_type, options) = match header {
+            DHCPv6Header::ClientServer {
+                message_type,
+                options,
+                ..
+            }
+            | DHCPv6Header::RelayAgentServer {
+                message_type,
+                options,
+                ..
+            }
+            | DHCPv6Header::DHCPv4Over6 {
+                message_type,
+                options,
+                ..
+            } => (*message_type, options),
+        };
+
+        let rule = message_rule(message_type);
+        let lenient =
+            |code: u16| message_type == DHCPv6MessageType::InformationRequest && code == 2;
+        self.check_options(options, rule.as_ref(), lenient);
+    }
+
+    fn check_options<F>(&mut self, options: &[DHCPv6Option], rule: Option<&Rule>, lenient: F)
+    where
+        F: Fn(u16) -> bool,
+    {
+        let codes: Vec<u16> = options
+            .iter()
+            .map(|option| option.code().to_u16())
+            .collect();
+
+        for (index, code) in codes.iter().enumerate() {
+            match codes[..index].iter().filter(|c| *c == code).count() {
+                0 => {}
+                1 if SINGLETON_OPTIONS.contains(code) => {
+                    self.report(
+                        Severity::Error,
+                        ViolationKind::DuplicateOption { code: *code },
+                    );
+                    continue;
+                }
+                _ => continue,
+            }
+
+            if let Some(rule) = rule {
+                if CHECKED_OPTIONS.contains(code) && !rule.allowed.contains(code) {
+                    let severity = if lenient(*code) {
+                        Severity::Warning
+                    } else {
+                        Severity::Error
+                    };
+                    self.report(severity, ViolationKind::UnexpectedOption { code: *code });
+                }
+            }
+        }
+
+        if let Some(rule) = rule {
+            for code in rule.required {
+                if !codes.contains(code) {
+                    self.report(
+                        Severity::Error,
+                        ViolationKind::MissingOption { code: *code },
+                    );
+                }
+            }
+        }
+
+        for option in options {
+            if let DHCPv6Option::Malformed { code, .. } = option {
+                self.report(
+                    Severity::Warning,
+                    ViolationKind::MalformedOption { code: *code },
+                );
+            }
+            self.check_option(option);
+        }
+    }
+
+    fn check_option(&mut self, option: &DHCPv6Option) {
+        let code = option.code().to_u16();
+        self.path.push(code);
+
+        match option {
+            DHCPv6Option::RelayMessage { data } => match parse_dhcpv6_header(data) {
+                Ok((_, header)) => self.check_header(&header),
+                Err(_) => self.report(Severity::Error, ViolationKind::MalformedRelayMessage),
+            },
+            // RFC 4704 section 4.1.
+            DHCPv6Option::ClientFqdn {
+                server_update,
+                no_update,
+                mbz_flags,
+                ..
+            } => {
+                if *server_update && *no_update {
+                    self.report(Severity::Error, ViolationKind::ConflictingFqdnFlags);
+                }
+                if *mbz_flags != 0 {
+                    self.report(Severity::Warning, ViolationKind::NonZeroMbzFlags { code });
+                }
+            }
+            _ => {
+                if let Some(rule) = encapsulated_rule(option) {
+                    self.check_options(option.options(), Some(&rule), |_| false);
+                }
+            }
+        }
+
+        self.path.pop();
+    }
+}
+
+pub fn validate(header: &DHCPv6Header) -> Vec<Violation> {
+    let mut validator = Validator {
+        violations: Vec::new(),
+        path: Vec::new(),
+    };
+    validator.check_header(header);
+
+    validator.violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::MessageBuilder;
+    use crate::config::ParseConfig;
+    use crate::structs::duid::Duid;
+    use crate::structs::header::{parse_message, parse_message_with};
+    use crate::structs::status_code::StatusCode;
+    use alloc::borrow::Cow;
+    use alloc::string::ToString;
+
+    fn duid() -> Duid<'static> {
+        Duid::LinkLayer {
+            hardware_type: 1,
+            link_layer_address: Cow::Borrowed(b"\x00\x11\x22\xaa\xbb\xcc"),
+        }
+    }
+
+    fn violations(bytes: &[u8]) -> Vec<(Severity, ViolationKind, Vec<u16>)> {
+        validate(&parse_message(bytes).unwrap())
+            .into_iter()
+            .map(|v| (v.severity, v.kind, v.path))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_compliant_messages() {
+        let solicit = MessageBuilder::solicit(1)
+            .client_id(&duid())
+            .elapsed(0)
+            .ia_na(1, |ia| ia)
+            .oro(&[23, 24])
+            .build()
+            .unwrap();
+        assert_eq!(violations(&solicit), vec![]);
+
+        let reply = MessageBuilder::reply(1)
+            .client_id(&duid())
+            .server_id(&duid())
+            .ia_pd(1, |ia| {
+                ia.prefix("2001:db8::".parse().unwrap(), 48, 1, 2)
+                    .status(StatusCode::Success, "")
+            })
+            .build()
+            .unwrap();
+        assert_eq!(violations(&reply), vec![]);
+
+        let relay =
+            MessageBuilder::relay_forward(0, "::".parse().unwrap(), "fe80::1".parse().unwrap())
+                .interface_id(b"eth0")
+                .relay_message(&solicit)
+                .build()
+                .unwrap();
+        assert_eq!(violations(&relay), vec![]);
+
+        let authentication = DHCPv6Option::Authentication {
+            protocol: 3,
+            algorithm: 1,
+            rdm: 0,
+            replay_detection: 1,
+            authentication_information: Cow::Borrowed(&b"key"[..]),
+        };
+        let vendor_options = DHCPv6Option::VendorSpecificInformation {
+            enterprise_number: 4491,
+            data: Cow::Borrowed(&b"\x00\x01\x00\x00"[..]),
+        };
+        let relay =
+            MessageBuilder::relay_forward(0, "::".parse().unwrap(), "fe80::1".parse().unwrap())
+                .option(&vendor_options)
+                .option(&authentication)
+                .relay_message(&solicit)
+                .build()
+                .unwrap();
+        assert_eq!(violations(&relay), vec![]);
+
+        let confirm = MessageBuilder::confirm(1)
+            .client_id(&duid())
+            .elapsed(0)
+            .option(&authentication)
+            .build()
+            .unwrap();
+        assert_eq!(violations(&confirm), vec![]);
+
+        let solicit = MessageBuilder::solicit(1)
+            .client_id(&duid())
+            .elapsed(0)
+            .option(&authentication)
+            .build()
+            .unwrap();
+        assert_eq!(violations(&solicit), vec![]);
+    }
+
+    #[test]
+    fn test_validate_violations() {
+        let solicit = MessageBuilder::solicit(1)
+            .server_id(&duid())
+            .elapsed(0)
+            .elapsed(0)
+            .elapsed(0)
+            .build()
+            .unwrap();
+        assert_eq!(
+            violations(&solicit),
+            vec![
+                (
+                    Severity::Error,
+                    ViolationKind::UnexpectedOption { code: 2 },
+                    vec![]
+                ),
+                (
+                    Severity::Error,
+                    ViolationKind::DuplicateOption { code: 8 },
+                    vec![]
+                ),
+                (
+                    Severity::Error,
+                    ViolationKind::MissingOption { code: 1 },
+                    vec![]
+                ),
+            ]
+        );
+
+        let reply = MessageBuilder::reply(1)
+            .server_id(&duid())
+            .ia_na(1, |ia| {
+                ia.option(&DHCPv6Option::IdentityAssociationPrefix {
+                    prefered_lifetime: 1,
+                    valid_lifetime: 2,
+                    prefix_length: 48,
+                    prefix: "2001:db8::".parse().unwrap(),
+                    options: vec![],
+                })
+            })
+            .build()
+            .unwrap();
+        assert_eq!(
+            violations(&reply),
+            vec![(
+                Severity::Error,
+                ViolationKind::UnexpectedOption { code: 26 },
+                vec![3]
+            )]
+        );
+
+        let information_request = MessageBuilder::information_request(1)
+            .server_id(&duid())
+            .elapsed(0)
+            .build()
+            .unwrap();
+        assert_eq!(
+            violations(&information_request),
+            vec![(
+                Severity::Warning,
+                ViolationKind::UnexpectedOption { code: 2 },
+                vec![]
+            )]
+        );
+
+        let relay =
+            MessageBuilder::relay_forward(0, "::".parse().unwrap(), "fe80::1".parse().unwrap())
+                .relay_message(&MessageBuilder::request(1).build().unwrap())
+                .build()
+                .unwrap();
+        let reply = b"\x07\x00\x00\x01\
+              \x00\x02\x00\x0a\x00\x03\x00\x01\x00\x11\x22\xaa\xbb\xcc\
+              \x00\x07\x00\x02\x00\xff";
+        let header = parse_message_with(&ParseConfig::tolerant(), &reply[..]).unwrap();
+        assert_eq!(
+            validate(&header)
+                .into_iter()
+                .map(|v| (v.severity, v.kind, v.path))
+                .collect::<Vec<_>>(),
+            vec![(
+                Severity::Warning,
+                ViolationKind::MalformedOption { code: 7 },
+                vec![]
+            )]
+        );
+
+        let solicit = b"\x01\x00\x00\x01\
+              \x00\x01\x00\x0a\x00\x03\x00\x01\x00\x11\x22\xaa\xbb\xcc\
+              \x00\x08\x00\x02\x00\x00\
+              \x00\x27\x00\x06\x05\x04host";
+        assert_eq!(
+            violations(&solicit[..]),
+            vec![(
+                Severity::Error,
+                ViolationKind::ConflictingFqdnFlags,
+                vec![39]
+            )]
+        );
+
+        let solicit = b"\x01\x00\x00\x01\
+              \x00\x01\x00\x0a\x00\x03\x00\x01\x00\x11\x22\xaa\xbb\xcc\
+              \x00\x08\x00\x02\x00\x00\
+              \x00\x27\x00\x06\x21\x04host";
+        assert_eq!(
+            violations(&solicit[..]),
+            vec![(
+                Severity::Warning,
+                ViolationKind::NonZeroMbzFlags { code: 39 },
+                vec![39]
+            )]
+        );
+
+        let found = validate(&parse_message(&relay).unwrap());
+        assert_eq!(found.len(), 3);
+        assert_eq!(
+            found[0].to_string(),
+            "error: required option 1 missing (in option 9)"
+        );
+
+        let relay =
+            MessageBuilder::relay_forward(0, "::".parse().unwrap(), "fe80::1".parse().unwrap())
+                .relay_message(b"\x01\x00")
+                .build()
+                .unwrap();
+        assert_eq!(
+            violations(&relay),
+            vec![(
+                Severity::Error,
+                ViolationKind::MalformedRelayMessage,
+                vec![9]
+            )]
+        );
+    }
+}
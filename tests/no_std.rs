@@ -0,0 +1,24 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+fn build_no_std_check(features: &str) {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let status = Command::new(env!("CARGO"))
+        .current_dir(&manifest_dir)
+        .args(["build", "--quiet", "-p", "dhcpv6-parser-no-std-check"])
+        .args(["--features", features])
+        .env("CARGO_TARGET_DIR", manifest_dir.join("target/no_std"))
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success());
+}
+
+#[test]
+fn test_no_std_build() {
+    build_no_std_check("");
+}
+
+#[test]
+fn test_no_std_alloc_build() {
+    build_no_std_check("alloc");
+}